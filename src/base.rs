@@ -1,23 +1,37 @@
-use crate::block::BLOCK_SIZE;
+use crate::block::{Aiming, Block, BLOCK_SIZE};
 use crate::collision_sounds::CollisionSound;
 use crate::consts::BASE_COLLISION_GROUP;
 use bevy::prelude::*;
 use bevy::sprite::Anchor;
+use bevy::utils::HashSet;
 use bevy_rapier2d::geometry::Collider;
-use bevy_rapier2d::plugin::systems::apply_scale;
-use bevy_rapier2d::prelude::{CollisionGroups, Friction, Group, RigidBody, Velocity};
+use bevy_rapier2d::prelude::{
+    ColliderMassProperties, CollisionGroups, Damping, Friction, Group, ImpulseJoint, RapierContext,
+    ReadMassProperties, RevoluteJointBuilder, RigidBody, Sleeping, Velocity,
+};
 
-use crate::cursor_system::CursorCoords;
 use crate::environment::rain::DarkenSpriteOnRain;
 use crate::level::{Level, LevelLifecycle};
 use crate::state::LevelState;
-use crate::HORIZONTAL_VIEWPORT_SIZE;
+use crate::{ASSET_SCALE, HORIZONTAL_VIEWPORT_SIZE};
 
 pub struct BasePlugin;
 
 impl Plugin for BasePlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(LevelState::Playing), setup_base);
+        app.add_systems(OnEnter(LevelState::Playing), setup_base)
+            .add_systems(
+                Update,
+                (
+                    keyboard_base_movement_system,
+                    path_base_movement_system,
+                    turntable_system,
+                    base_load_system.after(path_base_movement_system),
+                )
+                    .run_if(
+                        in_state(LevelState::Playing).or_else(in_state(LevelState::KeepPlaying)),
+                    ),
+            );
     }
 }
 
@@ -28,8 +42,29 @@ pub enum BaseType {
     T4,
     T7,
     T9,
+    /// Base of any width, drawn by tiling the middle of the T2 texture.
+    /// Optional steps raise parts of the top.
+    Procedural {
+        width: f32,
+        steps: &'static [BaseStep],
+    },
 }
 
+/// Raised section on top of a procedural base
+#[derive(Debug, Clone)]
+pub struct BaseStep {
+    /// Center of the step relative to the center of the base
+    pub x: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+/// Pixel columns of the T2 texture that are used as left and right edge of procedural bases,
+/// everything in between is repeated to fill the width
+const TILE_TEXTURE_WIDTH: f32 = 466.0;
+const TILE_TEXTURE_HEIGHT: f32 = 925.0;
+const TILE_EDGE_WIDTH: f32 = 40.0;
+
 impl BaseType {
     pub fn name(&self) -> &str {
         match self {
@@ -38,6 +73,7 @@ impl BaseType {
             BaseType::T4 => "t-4",
             BaseType::T7 => "t-7",
             BaseType::T9 => "t-9",
+            BaseType::Procedural { .. } => "t-2",
         }
     }
 
@@ -55,18 +91,119 @@ impl BaseType {
             BaseType::T4 => 762.0,
             BaseType::T7 => 1324.0,
             BaseType::T9 => 1652.0,
+            BaseType::Procedural { width, .. } => width / ASSET_SCALE,
         }
     }
 
+    pub fn collider(&self, height: f32) -> Collider {
+        let width = self.width();
+        let BaseType::Procedural { steps, .. } = self else {
+            return Collider::cuboid(width / 2.0, height / 2.0);
+        };
+
+        let mut shapes = vec![(Vec2::ZERO, 0.0, Collider::cuboid(width / 2.0, height / 2.0))];
+        for step in steps.iter() {
+            shapes.push((
+                Vec2::new(step.x, (height + step.height) / 2.0),
+                0.0,
+                Collider::cuboid(step.width / 2.0, step.height / 2.0),
+            ));
+        }
+        Collider::compound(shapes)
+    }
+
     pub fn asset(&self) -> String {
         format!("bases/{}.png", self.name())
     }
 }
 
+#[derive(Debug, Clone)]
+pub enum BaseMovement {
+    Static,
+    /// Moved left and right by the player with J and L
+    Keyboard {
+        max_speed: f32,
+    },
+    /// Follows the waypoints in a loop, waypoints are relative to the base translation
+    Path {
+        waypoints: &'static [Vec2],
+        speed: f32,
+    },
+}
+
+/// How the base is attached to the world. Mounted bases are dynamic bodies on a revolute joint
+/// around their center, their movement moves the pivot instead of the base itself.
+#[derive(Debug, Clone)]
+pub enum BaseMount {
+    Fixed,
+    /// Tilts freely under the weight of the tower, up to `max_angle` radians to either side
+    Seesaw {
+        max_angle: f32,
+    },
+    /// Driven by a motor, rotates back and forth between `-max_angle` and `max_angle`
+    Turntable {
+        angular_speed: f32,
+        max_angle: f32,
+    },
+}
+
+const MOUNTED_BASE_DENSITY: f32 = 2.0;
+const SEESAW_DAMPING: f32 = 0.5;
+const TURNTABLE_MOTOR_FACTOR: f32 = 5.0;
+
 #[derive(Component)]
 pub struct Base;
 
-pub fn setup_base(mut commands: Commands, mut assets: ResMut<AssetServer>, mut level: Res<Level>) {
+/// Kinematic body the joint of a mounted base is attached to
+#[derive(Component)]
+pub struct BasePivot;
+
+/// Mass of the blocks resting on the base, directly or through other blocks.
+/// Once it exceeds `max` the base sinks and tilts towards the heavier side,
+/// and comes back up once the load is removed.
+#[derive(Component, Debug)]
+pub struct BaseLoad {
+    pub max: f32,
+    pub current: f32,
+    pub overloaded: bool,
+    /// Meters the base sank below its rest position
+    pub sunk: f32,
+    /// Radians the base tilted away from its rest rotation
+    pub tilted: f32,
+}
+
+impl BaseLoad {
+    pub fn ratio(&self) -> f32 {
+        if self.max <= 0.0 {
+            return 0.0;
+        }
+        self.current / self.max
+    }
+}
+
+/// Sink speed in meters per second for every 100% of overload
+const OVERLOAD_SINK_SPEED: f32 = 0.3;
+const MAX_OVERLOAD_SINK_SPEED: f32 = 1.0;
+const MAX_OVERLOAD_SINK_DEPTH: f32 = 1.5;
+const OVERLOAD_RECOVER_SPEED: f32 = 0.2;
+const OVERLOAD_TILT_SPEED: f32 = 0.05;
+const MAX_OVERLOAD_TILT: f32 = 0.3;
+
+#[derive(Component, Debug)]
+pub struct Turntable {
+    pub angular_speed: f32,
+    pub max_angle: f32,
+    pub rest_rotation: f32,
+}
+
+#[derive(Component, Debug)]
+pub struct MovingBase {
+    pub movement: BaseMovement,
+    pub origin: Vec2,
+    pub next_waypoint: usize,
+}
+
+pub fn setup_base(mut commands: Commands, assets: ResMut<AssetServer>, level: Res<Level>) {
     let height = BLOCK_SIZE;
 
     // Since the spot in the bg image is not centered, we need to offset the base a bit
@@ -81,20 +218,19 @@ pub fn setup_base(mut commands: Commands, mut assets: ResMut<AssetServer>, mut l
 
         let texture = assets.load(base.base_type.asset());
 
-        commands
+        let translation = base.translation + additional_transform + Vec2::new(0.0, -height / 2.0);
+
+        let entity = commands
             .spawn((
                 CollisionSound::default(),
                 Base,
                 LevelLifecycle,
                 SpatialBundle::from(
-                    Transform::from_translation(Vec3::from((
-                        base.translation + additional_transform + Vec2::new(0.0, -height / 2.0),
-                        0.0,
-                    )))
-                    .with_rotation(Quat::from_rotation_z(base.rotation)),
+                    Transform::from_translation(Vec3::from((translation, 0.0)))
+                        .with_rotation(Quat::from_rotation_z(base.rotation)),
                 ),
                 RigidBody::KinematicVelocityBased,
-                Collider::cuboid(width / 2.0, height / 2.0),
+                base.base_type.collider(height),
                 Friction::coefficient(level.friction),
                 Velocity::linear(Vec2::new(0.0, 0.0)),
                 CollisionGroups {
@@ -103,6 +239,28 @@ pub fn setup_base(mut commands: Commands, mut assets: ResMut<AssetServer>, mut l
                 },
             ))
             .with_children(|parent| {
+                if let BaseType::Procedural { steps, .. } = base.base_type {
+                    spawn_tiled_sprites(
+                        parent,
+                        &texture,
+                        0.0,
+                        width,
+                        height / 2.0,
+                        TILE_TEXTURE_HEIGHT,
+                    );
+                    for step in steps.iter() {
+                        spawn_tiled_sprites(
+                            parent,
+                            &texture,
+                            step.x,
+                            step.width,
+                            height / 2.0 + step.height,
+                            step.height / ASSET_SCALE,
+                        );
+                    }
+                    return;
+                }
+
                 parent.spawn((
                     SpriteBundle {
                         // transform: Transform::from_xyz(0.0, 0.0, 0.0),
@@ -117,10 +275,151 @@ pub fn setup_base(mut commands: Commands, mut assets: ResMut<AssetServer>, mut l
                     },
                     DarkenSpriteOnRain(1.0),
                 ));
+            })
+            .id();
+
+        if let Some(max_load) = base.max_load {
+            commands.entity(entity).insert(BaseLoad {
+                max: max_load,
+                current: 0.0,
+                overloaded: false,
+                sunk: 0.0,
+                tilted: 0.0,
             });
+        }
+
+        let moving_entity = match base.mount {
+            BaseMount::Fixed => entity,
+            BaseMount::Seesaw { max_angle } => {
+                commands.entity(entity).insert(Damping {
+                    linear_damping: 0.0,
+                    angular_damping: SEESAW_DAMPING,
+                });
+                mount_base(
+                    &mut commands,
+                    entity,
+                    translation,
+                    base.rotation,
+                    RevoluteJointBuilder::new().limits([-max_angle, max_angle]),
+                )
+            }
+            BaseMount::Turntable {
+                angular_speed,
+                max_angle,
+            } => {
+                commands.entity(entity).insert(Turntable {
+                    angular_speed,
+                    max_angle,
+                    rest_rotation: base.rotation,
+                });
+                mount_base(
+                    &mut commands,
+                    entity,
+                    translation,
+                    base.rotation,
+                    RevoluteJointBuilder::new()
+                        .motor_velocity(angular_speed, TURNTABLE_MOTOR_FACTOR),
+                )
+            }
+        };
+
+        if !matches!(base.movement, BaseMovement::Static) {
+            commands.entity(moving_entity).insert(MovingBase {
+                movement: base.movement.clone(),
+                origin: translation,
+                next_waypoint: 0,
+            });
+        }
     }
 }
 
+/// Draws a section of a procedural base from the edges and the repeated middle of the texture.
+/// Only the top `image_height` pixels of the texture are used, so steps show just their roof.
+fn spawn_tiled_sprites(
+    parent: &mut ChildBuilder,
+    texture: &Handle<Image>,
+    center: f32,
+    width: f32,
+    top: f32,
+    image_height: f32,
+) {
+    let total = width / ASSET_SCALE;
+    let middle = TILE_TEXTURE_WIDTH - TILE_EDGE_WIDTH * 2.0;
+
+    let mut tiles = vec![Rect::new(0.0, 0.0, TILE_EDGE_WIDTH, image_height)];
+    let mut remaining = total - TILE_EDGE_WIDTH * 2.0;
+    while remaining > 0.0 {
+        let tile = remaining.min(middle);
+        tiles.push(Rect::new(
+            TILE_EDGE_WIDTH,
+            0.0,
+            TILE_EDGE_WIDTH + tile,
+            image_height,
+        ));
+        remaining -= tile;
+    }
+    tiles.push(Rect::new(
+        TILE_TEXTURE_WIDTH - TILE_EDGE_WIDTH,
+        0.0,
+        TILE_TEXTURE_WIDTH,
+        image_height,
+    ));
+
+    // Squeeze the edges together if the base is narrower than the edges of the texture
+    let scale = width / tiles.iter().map(|rect| rect.width()).sum::<f32>();
+
+    let mut x = center - width / 2.0;
+    for rect in tiles {
+        let size = rect.size() * scale;
+        parent.spawn((
+            SpriteBundle {
+                texture: texture.clone(),
+                transform: Transform::from_xyz(x, top, -7.0),
+                sprite: Sprite {
+                    anchor: Anchor::TopLeft,
+                    rect: Some(rect),
+                    custom_size: Some(size),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            DarkenSpriteOnRain(1.0),
+        ));
+        x += size.x;
+    }
+}
+
+/// Turns the base into a dynamic body hanging on a pivot at its center, returns the pivot
+fn mount_base(
+    commands: &mut Commands,
+    base: Entity,
+    translation: Vec2,
+    rotation: f32,
+    joint: RevoluteJointBuilder,
+) -> Entity {
+    let pivot = commands
+        .spawn((
+            BasePivot,
+            LevelLifecycle,
+            TransformBundle::from_transform(
+                Transform::from_translation(Vec3::from((translation, 0.0)))
+                    .with_rotation(Quat::from_rotation_z(rotation)),
+            ),
+            RigidBody::KinematicVelocityBased,
+            Velocity::zero(),
+        ))
+        .id();
+
+    commands.entity(base).insert((
+        RigidBody::Dynamic,
+        ColliderMassProperties::Density(MOUNTED_BASE_DENSITY),
+        ImpulseJoint::new(pivot, joint),
+        Sleeping::disabled(),
+    ));
+
+    pivot
+}
+
 // pub fn base_position(mycoords: Res<MyWorldCoords>, mut query: Query<&mut Transform, With<Base>>) {
 //     for mut transform in query.iter_mut() {
 //         transform.translation.x = mycoords.0.x;
@@ -128,18 +427,27 @@ pub fn setup_base(mut commands: Commands, mut assets: ResMut<AssetServer>, mut l
 //     }
 // }
 
-pub fn base_position(key_code: Res<Input<KeyCode>>, mut query: Query<&mut Velocity, With<Base>>) {
-    for mut velocity in query.iter_mut() {
-        let max_velocity = 100.0;
-        let increment = 1.0;
-        let decrement = 1.0;
+pub fn keyboard_base_movement_system(
+    key_code: Res<Input<KeyCode>>,
+    time: Res<Time>,
+    mut query: Query<(&mut Velocity, &MovingBase)>,
+) {
+    for (mut velocity, moving_base) in query.iter_mut() {
+        let BaseMovement::Keyboard { max_speed } = moving_base.movement else {
+            continue;
+        };
 
-        if key_code.pressed(KeyCode::Left) {
+        let max_velocity = max_speed;
+        // Reaches full speed in half a second
+        let increment = max_speed * 2.0 * time.delta_seconds();
+        let decrement = increment;
+
+        if key_code.pressed(KeyCode::J) {
             velocity.linvel.x -= increment;
             if velocity.linvel.x < -max_velocity {
                 velocity.linvel.x = -max_velocity;
             }
-        } else if key_code.pressed(KeyCode::Right) {
+        } else if key_code.pressed(KeyCode::L) {
             velocity.linvel.x += increment;
             if velocity.linvel.x > max_velocity {
                 velocity.linvel.x = max_velocity;
@@ -150,11 +458,138 @@ pub fn base_position(key_code: Res<Input<KeyCode>>, mut query: Query<&mut Veloci
             } else if velocity.linvel.x < 0.0 {
                 velocity.linvel.x += decrement
             }
-            if velocity.linvel.x.abs() < 1.0 {
+            if velocity.linvel.x.abs() < decrement {
                 velocity.linvel.x = 0.0;
             }
         }
+    }
+}
+
+pub fn path_base_movement_system(
+    time: Res<Time>,
+    mut query: Query<(&Transform, &mut Velocity, &mut MovingBase)>,
+) {
+    for (transform, mut velocity, mut moving_base) in query.iter_mut() {
+        let BaseMovement::Path { waypoints, speed } = moving_base.movement else {
+            continue;
+        };
+
+        if waypoints.is_empty() {
+            continue;
+        }
+
+        let target = moving_base.origin + waypoints[moving_base.next_waypoint];
+        let offset = target - transform.translation.xy();
+
+        if offset.length() <= speed * time.delta_seconds() {
+            moving_base.next_waypoint = (moving_base.next_waypoint + 1) % waypoints.len();
+        }
+
+        velocity.linvel = offset.normalize_or_zero() * speed;
+    }
+}
+
+/// Reverses the turntable motor once the base reaches one of its limits
+pub fn turntable_system(mut query: Query<(&Transform, &mut Turntable, &mut ImpulseJoint)>) {
+    for (transform, mut turntable, mut joint) in query.iter_mut() {
+        let (_, _, rotation) = transform.rotation.to_euler(EulerRot::XYZ);
+        let angle = rotation - turntable.rest_rotation;
+
+        let reached_limit = (turntable.angular_speed > 0.0 && angle >= turntable.max_angle)
+            || (turntable.angular_speed < 0.0 && angle <= -turntable.max_angle);
+
+        if reached_limit {
+            turntable.angular_speed = -turntable.angular_speed;
+            if let Some(revolute) = joint.data.as_revolute_mut() {
+                revolute.set_motor_velocity(turntable.angular_speed, TURNTABLE_MOTOR_FACTOR);
+            }
+        }
+    }
+}
+
+pub fn base_load_system(
+    rapier_context: Res<RapierContext>,
+    mut base_query: Query<(
+        Entity,
+        &mut Transform,
+        &mut BaseLoad,
+        Option<&mut MovingBase>,
+    )>,
+    block_query: Query<
+        (&Transform, &ReadMassProperties),
+        (With<Block>, Without<Aiming>, Without<BaseLoad>),
+    >,
+    time: Res<Time>,
+) {
+    let dt = time.delta_seconds();
+
+    for (entity, mut transform, mut load, moving_base) in base_query.iter_mut() {
+        let mut visited = HashSet::default();
+        visited.insert(entity);
+        let mut stack = vec![entity];
+        let mut mass = 0.0;
+        let mut moment = 0.0;
+
+        // Walk the contact graph, every block touching the base or a block on it adds to the load
+        while let Some(current) = stack.pop() {
+            for contact in rapier_context.contacts_with(current) {
+                if !contact.has_any_active_contacts() {
+                    continue;
+                }
+
+                let other = if contact.collider1() == current {
+                    contact.collider2()
+                } else {
+                    contact.collider1()
+                };
 
-        //transform.translation.y = mycoords.0.y;
+                if !visited.insert(other) {
+                    continue;
+                }
+
+                if let Ok((block_transform, mass_properties)) = block_query.get(other) {
+                    mass += mass_properties.mass;
+                    moment += mass_properties.mass * block_transform.translation.x;
+                    stack.push(other);
+                }
+            }
+        }
+
+        load.current = mass;
+        let overload = load.ratio() - 1.0;
+        load.overloaded = overload > 0.0;
+
+        let (sink, turn) = if load.overloaded {
+            let center = moment / mass;
+            let tilt = if center > transform.translation.x {
+                -OVERLOAD_TILT_SPEED
+            } else {
+                OVERLOAD_TILT_SPEED
+            } * dt;
+
+            let sink = (overload * OVERLOAD_SINK_SPEED).min(MAX_OVERLOAD_SINK_SPEED) * dt;
+            let turn = if (load.tilted + tilt).abs() < MAX_OVERLOAD_TILT {
+                tilt
+            } else {
+                0.0
+            };
+            (sink.min(MAX_OVERLOAD_SINK_DEPTH - load.sunk).max(0.0), turn)
+        } else {
+            // Float back up to the rest position once the load is off
+            let sink = -(OVERLOAD_RECOVER_SPEED * dt).min(load.sunk);
+            let turn = -load.tilted.signum() * (OVERLOAD_TILT_SPEED * dt).min(load.tilted.abs());
+            (sink, turn)
+        };
+
+        // Applied as an offset, the velocity belongs to the base movement
+        load.sunk += sink;
+        load.tilted += turn;
+        transform.translation.y -= sink;
+        transform.rotate_z(turn);
+
+        // Paths are relative to the origin, move it along so the path doesn't undo the sinking
+        if let Some(mut moving_base) = moving_base {
+            moving_base.origin.y -= sink;
+        }
     }
 }
@@ -6,7 +6,7 @@ use rand::Rng;
 
 use crate::collision_sounds::CollisionSound;
 use crate::consts::BLOCK_COLLISION_GROUP;
-use crate::effect::EffectType;
+use crate::effect::{EffectId, EnableEffect};
 use crate::floor::Floor;
 use crate::level::{Level, LevelLifecycle};
 use crate::state::LevelState;
@@ -31,6 +31,7 @@ impl Plugin for BlockPlugin {
 }
 
 #[derive(Resource, Debug)]
+#[allow(dead_code)]
 pub struct SpawnTimer(pub Timer);
 
 impl Default for SpawnTimer {
@@ -167,7 +168,7 @@ impl BlockType {
                     Collider::cuboid(half_size * 3.0, half_size),
                 ),
                 (
-                    Vec2::new(-1.0 * size, half_size),
+                    Vec2::new(-size, half_size),
                     0.0,
                     Collider::cuboid(half_size, half_size),
                 ),
@@ -251,7 +252,7 @@ impl BlockType {
 #[derive(Component, Debug)]
 pub struct Block {
     pub block_type: BlockType,
-    pub effect_type: Option<EffectType>,
+    pub effects: Vec<EffectId>,
     pub variant: usize,
     pub initial_rotation: f32,
 }
@@ -260,6 +261,7 @@ pub struct Block {
 pub struct Falling;
 
 #[derive(Component)]
+#[allow(dead_code)]
 pub struct TargetBeam;
 
 // This is a marker component that is set while the block is being aimed
@@ -286,13 +288,13 @@ impl Block {
     pub fn new(
         block_type: BlockType,
         variant: usize,
-        effect_type: Option<EffectType>,
+        effects: Vec<EffectId>,
         initial_rotation: f32,
     ) -> Self {
         Self {
             block_type,
             variant,
-            effect_type,
+            effects,
             initial_rotation,
         }
     }
@@ -314,12 +316,12 @@ impl Block {
 
     pub fn spawn(
         self,
-        mut commands: &mut Commands,
+        commands: &mut Commands,
         position: Vec2,
         assets: &AssetServer,
         level: &Level,
     ) -> Entity {
-        let effect_type = self.effect_type;
+        let effects = self.effects.clone();
         let block_type = self.block_type;
         let sprite = self.sprite(assets);
         let initial_rotation = self.initial_rotation;
@@ -363,35 +365,36 @@ impl Block {
             ))
             .id();
 
-        if let Some(effect) = effect_type {
-            effect.enable(&mut commands, assets, entity, block_type);
+        for (layer, effect) in effects.into_iter().enumerate() {
+            commands.add(EnableEffect {
+                entity,
+                effect,
+                block_type,
+                layer,
+            });
         }
         entity
     }
 }
 
 pub fn falling_block_collision(
-    mut commands: Commands,
     mut event_reader: EventReader<CollisionEvent>,
     mut query: Query<(Entity, &mut Block, &mut Velocity, &mut Transform, &Falling)>,
-    mut catcher_query: Query<
-        (Entity),
-        (Without<Falling>, Without<Aiming>, Without<TargetIndicator>),
-    >,
+    mut catcher_query: Query<Entity, (Without<Falling>, Without<Aiming>, Without<TargetIndicator>)>,
     mut falling_block_collision: EventWriter<FallingBlockCollision>,
 ) {
-    for event in event_reader.iter() {
+    for event in event_reader.read() {
         match event {
-            CollisionEvent::Started(collider1, collider2, flags) => {
+            CollisionEvent::Started(collider1, collider2, _flags) => {
                 [(collider1, collider2), (collider2, collider1)]
                     .into_iter()
                     .for_each(|(entity, catcher)| {
-                        let mut valid_catcher = catcher_query.get_mut(*catcher);
+                        let valid_catcher = catcher_query.get_mut(*catcher);
                         if valid_catcher.is_err() {
                             return;
                         }
 
-                        if let Ok((entity, mut block, mut velocity, mut transform, _)) =
+                        if let Ok((entity, _block, _velocity, _transform, _)) =
                             query.get_mut(*entity)
                         {
                             falling_block_collision.send(FallingBlockCollision {
@@ -421,14 +424,13 @@ pub fn block_stable_system(
 
 pub fn rotate_aimed_blocks(
     mut query: Query<(Entity, &mut Transform, With<Block>, With<Aiming>)>,
-    key_code: Res<Input<KeyCode>>,
     mouse_button: Res<Input<MouseButton>>,
     touch: Res<Touches>,
 ) {
     if mouse_button.just_pressed(MouseButton::Right)
         || (touch.iter().count() == 2 && touch.any_just_pressed())
     {
-        for (entity, mut transform, ..) in query.iter_mut() {
+        for (_entity, mut transform, ..) in query.iter_mut() {
             let vec3 = transform.translation;
             transform.rotate_around(vec3, Quat::from_rotation_z(FRAC_PI_2));
         }
@@ -438,23 +440,24 @@ pub fn rotate_aimed_blocks(
 pub fn despawn_dropped_blocks(
     mut commands: Commands,
     mut query: Query<(Entity, &Transform, With<Block>)>,
-    mut floor_query: Query<(&Transform, With<Floor>)>,
+    floor_query: Query<(&Transform, With<Floor>)>,
 ) {
     let (floor_transform, _) = floor_query.single();
-    for (entity, mut transform, ..) in query.iter_mut() {
+    for (entity, transform, ..) in query.iter_mut() {
         if transform.translation.y < floor_transform.translation.y {
             commands.entity(entity).despawn_recursive();
         }
     }
 }
 
+#[allow(dead_code)]
 pub fn despawn_target_beam(
     mut events: EventReader<CaughtBlock>,
     mut commands: Commands,
-    query: Query<(&Children)>,
+    query: Query<&Children>,
     children_query: Query<(Entity, With<TargetBeam>)>,
 ) {
-    for event in events.iter() {
+    for event in events.read() {
         let children = query.get(event.entity);
         if let Ok(children) = children {
             for child in children.iter() {
@@ -1,16 +1,21 @@
-use crate::block::{Block, Falling};
 use crate::launch_platform::LaunchPlatform;
 use crate::{MainCamera, HORIZONTAL_VIEWPORT_SIZE};
 use bevy::prelude::*;
+use rand::random;
 
 #[derive(Resource)]
 pub struct CameraMovement {
     pub height: f32,
+    /// Maximum random offset of the camera, used for screen shake
+    pub shake: f32,
 }
 
 impl Default for CameraMovement {
     fn default() -> Self {
-        Self { height: 0.0 }
+        Self {
+            height: 0.0,
+            shake: 0.0,
+        }
     }
 }
 
@@ -40,11 +45,15 @@ pub fn camera_movement_system(
         camera_movement.height -= increase;
     }
 
-    for (mut transform, global_transform, camera) in camera_query.iter_mut() {
+    for (mut transform, _global_transform, camera) in camera_query.iter_mut() {
         let viewport = camera.logical_viewport_size().unwrap();
 
         let scene_height = HORIZONTAL_VIEWPORT_SIZE * viewport.y / viewport.x;
 
-        transform.translation.y = camera_movement.height + scene_height / 2.0;
+        let shake =
+            (Vec2::new(random::<f32>(), random::<f32>()) * 2.0 - 1.0) * camera_movement.shake;
+
+        transform.translation.x = shake.x;
+        transform.translation.y = camera_movement.height + scene_height / 2.0 + shake.y;
     }
 }
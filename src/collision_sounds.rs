@@ -1,6 +1,6 @@
 use bevy::audio::{Volume, VolumeLevel};
 use bevy::prelude::*;
-use bevy_rapier2d::prelude::{CollisionEvent, ContactForceEvent, Velocity};
+use bevy_rapier2d::prelude::{CollisionEvent, Velocity};
 
 pub struct CollisionSoundPlugin;
 
@@ -58,15 +58,13 @@ pub fn collision_sound_system(
                 // volume is 0 at min_velocity, 1 at 2 * min_velocity, 2 at 3 * min_velocity
                 let volume = (relative_velocity * 0.01).min(1.0);
 
-                commands.spawn(
-                    (AudioBundle {
-                        source: sound,
-                        settings: PlaybackSettings {
-                            volume: Volume::Relative(VolumeLevel::new(volume)),
-                            ..PlaybackSettings::DESPAWN
-                        },
-                    }),
-                );
+                commands.spawn(AudioBundle {
+                    source: sound,
+                    settings: PlaybackSettings {
+                        volume: Volume::Relative(VolumeLevel::new(volume)),
+                        ..PlaybackSettings::DESPAWN
+                    },
+                });
             }
         }
     }
@@ -14,6 +14,7 @@ pub const VEHICLE_COLLISION_GROUP: Group = Group::GROUP_5;
 pub fn foreground_collision_groups() -> Group {
     FLOOR_COLLISION_GROUP | DEBRIS_COLLISION_GROUP | RAIN_COLLISION_GROUP | VEHICLE_COLLISION_GROUP
 }
+#[allow(dead_code)]
 pub fn building_collision_groups() -> Group {
     BLOCK_COLLISION_GROUP | BASE_COLLISION_GROUP
 }
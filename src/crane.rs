@@ -0,0 +1,229 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use bevy_rapier2d::rapier::dynamics::JointAxis;
+
+use crate::block::Aiming;
+use crate::launch_platform::ActiveLaunchPlatform;
+use crate::level::LevelLifecycle;
+use crate::state::LevelState;
+use crate::throw::{throw_system, Aim};
+
+pub struct CranePlugin;
+
+impl Plugin for CranePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                crane_control_system,
+                crane_hold_block_system,
+                crane_aim_system.before(throw_system),
+                crane_rope_system,
+            )
+                .run_if(in_state(LevelState::Playing).or_else(in_state(LevelState::KeepPlaying))),
+        );
+    }
+}
+
+pub const CRANE_START_ROPE_LENGTH: f32 = 3.0;
+pub const CRANE_MIN_ROPE_LENGTH: f32 = 1.0;
+pub const CRANE_MAX_ROPE_LENGTH: f32 = 12.0;
+/// Meters per second the rope is lowered or raised
+const CRANE_ROPE_SPEED: f32 = 4.0;
+const CRANE_MAX_SPEED: f32 = 6.0;
+
+/// Trolley running along the beam, the aiming block hangs below it on a rope joint
+#[derive(Component, Debug)]
+pub struct Crane {
+    pub min_x: f32,
+    pub max_x: f32,
+    pub rope_length: f32,
+}
+
+#[derive(Component, Debug)]
+pub struct CraneRope {
+    pub trolley: Entity,
+}
+
+/// Turns the launch platform into a crane trolley, called instead of spawning the cannon
+pub fn spawn_crane(
+    commands: &mut Commands,
+    trolley: Entity,
+    translation: Vec2,
+    min_x: f32,
+    max_x: f32,
+) {
+    commands.spawn((
+        LevelLifecycle,
+        SpriteBundle {
+            transform: Transform::from_xyz((min_x + max_x) / 2.0, translation.y + 0.5, -0.1),
+            sprite: Sprite {
+                color: Color::rgb(0.85, 0.65, 0.1),
+                custom_size: Some(Vec2::new(max_x - min_x + 2.0, 0.4)),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    ));
+
+    commands.spawn((
+        CraneRope { trolley },
+        LevelLifecycle,
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::rgb(0.15, 0.15, 0.15),
+                custom_size: Some(Vec2::new(0.06, 1.0)),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    ));
+
+    commands
+        .entity(trolley)
+        .insert(Crane {
+            min_x,
+            max_x,
+            rope_length: CRANE_START_ROPE_LENGTH,
+        })
+        .with_children(|parent| {
+            parent.spawn(SpriteBundle {
+                sprite: Sprite {
+                    color: Color::rgb(0.95, 0.75, 0.15),
+                    custom_size: Some(Vec2::new(1.4, 0.7)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            });
+        });
+}
+
+fn rope_joint(rope_length: f32) -> RopeJointBuilder {
+    RopeJointBuilder::new().limits([0.0, rope_length])
+}
+
+/// A/D moves the trolley, W/S raises and lowers the hook
+pub fn crane_control_system(
+    key_code: Res<Input<KeyCode>>,
+    time: Res<Time>,
+    mut query: Query<(&Transform, &mut Velocity, &mut Crane), With<ActiveLaunchPlatform>>,
+    mut block_query: Query<&mut ImpulseJoint, With<Aiming>>,
+) {
+    for (transform, mut velocity, mut crane) in query.iter_mut() {
+        let increment = CRANE_MAX_SPEED * 2.0 * time.delta_seconds();
+
+        let target = if key_code.pressed(KeyCode::A) {
+            -CRANE_MAX_SPEED
+        } else if key_code.pressed(KeyCode::D) {
+            CRANE_MAX_SPEED
+        } else {
+            0.0
+        };
+        velocity.linvel.x += (target - velocity.linvel.x).clamp(-increment, increment);
+        velocity.linvel.y = 0.0;
+
+        let x = transform.translation.x;
+        if (x <= crane.min_x && velocity.linvel.x < 0.0)
+            || (x >= crane.max_x && velocity.linvel.x > 0.0)
+        {
+            velocity.linvel.x = 0.0;
+        }
+
+        let rope_change = if key_code.pressed(KeyCode::S) {
+            CRANE_ROPE_SPEED
+        } else if key_code.pressed(KeyCode::W) {
+            -CRANE_ROPE_SPEED
+        } else {
+            continue;
+        };
+
+        crane.rope_length = (crane.rope_length + rope_change * time.delta_seconds())
+            .clamp(CRANE_MIN_ROPE_LENGTH, CRANE_MAX_ROPE_LENGTH);
+
+        for mut joint in block_query.iter_mut() {
+            let limits = [0.0, crane.rope_length];
+            joint.data.set_limits(JointAxis::X, limits);
+            joint.data.set_limits(JointAxis::Y, limits);
+        }
+    }
+}
+
+/// Hangs the aiming block from the rope of the active crane. It swings and collides like any
+/// other load until it is dropped. Blocks are taken off the rope again when a cannon takes over.
+pub fn crane_hold_block_system(
+    mut commands: Commands,
+    crane_query: Query<(Entity, &Transform, &Crane), (With<ActiveLaunchPlatform>, Without<Aiming>)>,
+    mut block_query: Query<(Entity, &mut Transform, Option<&ImpulseJoint>), With<Aiming>>,
+) {
+    let crane = crane_query.get_single().ok();
+
+    for (entity, mut block_transform, joint) in block_query.iter_mut() {
+        match (crane, joint) {
+            (Some((trolley, _, _)), Some(joint)) if joint.parent == trolley => {}
+            (Some((trolley, transform, crane)), _) => {
+                let position = transform.translation.xy() - Vec2::Y * crane.rope_length;
+                block_transform.translation = Vec3::from((position, 0.0));
+                commands.entity(entity).remove::<Sensor>().insert((
+                    RigidBody::Dynamic,
+                    Velocity::zero(),
+                    ImpulseJoint::new(trolley, rope_joint(crane.rope_length)),
+                    Visibility::Visible,
+                ));
+            }
+            (None, Some(_)) => {
+                commands.entity(entity).remove::<ImpulseJoint>().insert((
+                    RigidBody::KinematicVelocityBased,
+                    Sensor,
+                    Velocity::zero(),
+                    Visibility::Hidden,
+                ));
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+/// Dropped blocks keep their swing, so that is what the preview shows
+pub fn crane_aim_system(
+    mut aim: ResMut<Aim>,
+    crane_query: Query<(), (With<Crane>, With<ActiveLaunchPlatform>)>,
+    block_query: Query<&Velocity, (With<Aiming>, With<ImpulseJoint>)>,
+) {
+    if crane_query.is_empty() {
+        return;
+    }
+
+    let Ok(velocity) = block_query.get_single() else {
+        return;
+    };
+
+    aim.direction = velocity.linvel.normalize_or_zero();
+    aim.force = velocity.linvel.length();
+}
+
+/// Draws the rope down to the hanging block, or down to the hook position while nothing hangs
+pub fn crane_rope_system(
+    mut rope_query: Query<(&CraneRope, &mut Transform)>,
+    trolley_query: Query<(&Transform, &Crane), Without<CraneRope>>,
+    block_query: Query<(&Transform, &ImpulseJoint), (With<Aiming>, Without<CraneRope>)>,
+) {
+    for (rope, mut transform) in rope_query.iter_mut() {
+        let Ok((trolley, crane)) = trolley_query.get(rope.trolley) else {
+            continue;
+        };
+
+        let start = trolley.translation.xy();
+        let end = block_query
+            .iter()
+            .find(|(_, joint)| joint.parent == rope.trolley)
+            .map_or(start - Vec2::Y * crane.rope_length, |(block, _)| {
+                block.translation.xy()
+            });
+        let offset = end - start;
+        let center = start + offset / 2.0;
+
+        *transform = Transform::from_xyz(center.x, center.y, 0.05)
+            .with_rotation(Quat::from_rotation_z(Vec2::Y.angle_between(offset)))
+            .with_scale(Vec3::new(1.0, offset.length(), 1.0));
+    }
+}
@@ -0,0 +1,64 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::effect::{EffectDefinition, EffectId, RegisterEffect};
+
+pub struct AnchorPlugin;
+
+impl Plugin for AnchorPlugin {
+    fn build(&self, app: &mut App) {
+        let effect = AnchorEffect::default();
+        app.register_effect(
+            EffectDefinition::new(ANCHOR)
+                .with_group("friction")
+                .with_bundle((effect.physics(), effect))
+                .on_catch(anchor_settle),
+        );
+    }
+}
+
+pub const ANCHOR: EffectId = EffectId("anchor");
+
+/// Very heavy block with a lot of friction, meant to stabilise the base of a tower.
+/// Once caught it is strongly damped so it settles quickly and resists sliding.
+#[derive(Component, Debug, Clone)]
+pub struct AnchorEffect {
+    pub density: f32,
+    pub friction: f32,
+    pub damping: f32,
+}
+
+impl Default for AnchorEffect {
+    fn default() -> Self {
+        Self {
+            density: 5.0,
+            friction: 1.5,
+            damping: 2.0,
+        }
+    }
+}
+
+impl AnchorEffect {
+    pub fn physics(&self) -> (ColliderMassProperties, Friction) {
+        (
+            ColliderMassProperties::Density(self.density),
+            Friction {
+                coefficient: self.friction,
+                // Rain should not make anchors slippery
+                combine_rule: CoefficientCombineRule::Max,
+            },
+        )
+    }
+}
+
+pub fn anchor_settle(world: &mut World, entity: Entity) {
+    if let Some(damping) = world
+        .get::<AnchorEffect>(entity)
+        .map(|effect| effect.damping)
+    {
+        world.entity_mut(entity).insert(Damping {
+            linear_damping: damping,
+            angular_damping: damping,
+        });
+    }
+}
@@ -0,0 +1,97 @@
+use std::sync::Arc;
+
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::block::{Aiming, Block};
+use crate::effect::glue::GlueJoint;
+use crate::effect::{EffectDefinition, EffectId, RegisterEffect, TrajectoryModifier};
+use crate::level::Level;
+use crate::throw::TargetIndicator;
+use crate::PHYSICS_DT;
+
+pub struct BalloonPlugin;
+
+impl Plugin for BalloonPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_effect(
+            EffectDefinition::new(BALLOON)
+                .with_bundle(BalloonEffect::default())
+                .with_prediction(Arc::new(balloon_prediction)),
+        )
+        .add_systems(FixedUpdate, balloon_lift_system);
+    }
+}
+
+pub const BALLOON: EffectId = EffectId("balloon");
+
+/// Pulls the block upwards, cancelling out most of its weight.
+/// Blocks glued to the balloon are lifted as well, so overhangs can be built.
+#[derive(Component, Debug, Clone)]
+pub struct BalloonEffect {
+    /// Fraction of gravity that is cancelled out
+    pub lift: f32,
+}
+
+impl Default for BalloonEffect {
+    fn default() -> Self {
+        Self { lift: 0.9 }
+    }
+}
+
+impl BalloonEffect {
+    pub fn acceleration(&self, gravity: f32) -> Vec2 {
+        Vec2::Y * -gravity * self.lift
+    }
+
+    pub fn impulse(&self, mass: f32, gravity: f32) -> Vec2 {
+        self.acceleration(gravity) * mass * PHYSICS_DT
+    }
+}
+
+/// Lift of the aimed balloon, applied to the trajectory preview
+pub struct BalloonLift(pub Vec2);
+
+impl TrajectoryModifier for BalloonLift {
+    fn acceleration(&self) -> Vec2 {
+        self.0
+    }
+}
+
+pub fn balloon_prediction(world: &mut World, aimed: Entity) -> Option<Box<dyn TrajectoryModifier>> {
+    let gravity = world.resource::<Level>().physics.gravity;
+    world.get::<BalloonEffect>(aimed).map(|effect| {
+        Box::new(BalloonLift(effect.acceleration(gravity))) as Box<dyn TrajectoryModifier>
+    })
+}
+
+pub fn balloon_lift_system(
+    balloon_query: Query<
+        (Entity, &BalloonEffect),
+        (With<Block>, Without<Aiming>, Without<TargetIndicator>),
+    >,
+    mut block_query: Query<
+        (&mut ExternalImpulse, &ReadMassProperties),
+        (With<Block>, Without<Aiming>),
+    >,
+    glue_joints: Query<(&Parent, &ImpulseJoint), With<GlueJoint>>,
+    level: Res<Level>,
+) {
+    for (balloon, effect) in balloon_query.iter() {
+        let glued = glue_joints.iter().filter_map(|(parent, joint)| {
+            if joint.parent == balloon {
+                Some(parent.get())
+            } else if parent.get() == balloon {
+                Some(joint.parent)
+            } else {
+                None
+            }
+        });
+
+        for entity in std::iter::once(balloon).chain(glued) {
+            if let Ok((mut impulse, mass)) = block_query.get_mut(entity) {
+                impulse.impulse += effect.impulse(mass.mass, level.physics.gravity);
+            }
+        }
+    }
+}
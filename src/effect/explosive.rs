@@ -0,0 +1,168 @@
+use bevy::audio::{Volume, VolumeLevel};
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+use bevy_rapier2d::prelude::*;
+
+use crate::block::{Aiming, Block};
+use crate::debris::destroy_block;
+use crate::effect::{EffectDefinition, EffectId, RegisterEffect};
+use crate::level::{LevelLifecycle, UpdateLevelStats};
+use crate::state::LevelState;
+
+pub struct ExplosivePlugin;
+
+impl Plugin for ExplosivePlugin {
+    fn build(&self, app: &mut App) {
+        app.register_effect(
+            EffectDefinition::new(EXPLOSIVE)
+                .with_bundle(ExplosiveEffect::default())
+                .on_catch(arm_explosive),
+        )
+        .add_systems(
+            Update,
+            (explosive_detonation_system, explosion_flash_system)
+                .run_if(in_state(LevelState::Playing).or_else(in_state(LevelState::KeepPlaying))),
+        );
+    }
+}
+
+pub const EXPLOSIVE: EffectId = EffectId("explosive");
+
+pub const EXPLOSIVE_FUSE_SECONDS: f32 = 4.0;
+
+/// Detonates a few seconds after the block has been caught, or when the player presses X.
+/// Blocks within `inner_radius` are turned into debris, blocks within `radius` are pushed away.
+#[derive(Component, Debug, Clone)]
+pub struct ExplosiveEffect {
+    pub fuse: Option<Timer>,
+    pub radius: f32,
+    pub inner_radius: f32,
+    pub force: f32,
+}
+
+impl Default for ExplosiveEffect {
+    fn default() -> Self {
+        Self {
+            fuse: None,
+            radius: 6.0,
+            inner_radius: 2.5,
+            force: 30.0,
+        }
+    }
+}
+
+#[derive(Component, Debug)]
+pub struct ExplosionFlash(pub Timer);
+
+pub fn arm_explosive(world: &mut World, entity: Entity) {
+    if let Some(mut effect) = world.get_mut::<ExplosiveEffect>(entity) {
+        if effect.fuse.is_none() {
+            effect.fuse = Some(Timer::from_seconds(EXPLOSIVE_FUSE_SECONDS, TimerMode::Once));
+        }
+    }
+}
+
+pub fn explosive_detonation_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    key_code: Res<Input<KeyCode>>,
+    mut explosives: Query<(Entity, &mut ExplosiveEffect, &Transform), Without<Aiming>>,
+    mut blocks: Query<
+        (Entity, &Block, &Transform, &Velocity, &mut ExternalImpulse),
+        Without<Aiming>,
+    >,
+    mut update_level_stats_events: EventWriter<UpdateLevelStats>,
+    assets: Res<AssetServer>,
+) {
+    let detonate_all = key_code.just_pressed(KeyCode::X);
+
+    let mut detonations = vec![];
+
+    for (entity, mut effect, transform) in explosives.iter_mut() {
+        let fuse_finished = effect
+            .fuse
+            .as_mut()
+            .map(|fuse| fuse.tick(time.delta()).finished())
+            .unwrap_or(false);
+
+        // Only caught explosives are armed, falling ones can't be set off early
+        let armed = effect.fuse.is_some();
+
+        if fuse_finished || (detonate_all && armed) {
+            detonations.push((
+                entity,
+                transform.translation.xy(),
+                effect.radius,
+                effect.inner_radius,
+                effect.force,
+            ));
+        }
+    }
+
+    let mut destroyed = HashSet::new();
+
+    for (source, position, radius, inner_radius, force) in detonations {
+        // Already blown up by a previous explosion this frame
+        if destroyed.contains(&source) {
+            continue;
+        }
+
+        for (entity, block, transform, velocity, mut impulse) in blocks.iter_mut() {
+            if destroyed.contains(&entity) {
+                continue;
+            }
+
+            let offset = transform.translation.xy() - position;
+            let distance = offset.length();
+
+            if entity == source || distance < inner_radius {
+                destroy_block(&mut commands, &assets, entity, block, transform, velocity);
+                destroyed.insert(entity);
+
+                // The explosive itself isn't charged as a demolished block
+                if entity != source {
+                    update_level_stats_events.send(UpdateLevelStats::BlockDemolished);
+                }
+            } else if distance < radius {
+                impulse.impulse += offset.normalize() * force * (1.0 - distance / radius);
+            }
+        }
+
+        commands.spawn((
+            ExplosionFlash(Timer::from_seconds(0.3, TimerMode::Once)),
+            LevelLifecycle,
+            SpriteBundle {
+                transform: Transform::from_xyz(position.x, position.y, 1.0),
+                texture: assets.load("circle.png"),
+                sprite: Sprite {
+                    color: Color::rgba(1.0, 0.6, 0.1, 0.8),
+                    custom_size: Some(Vec2::splat(radius * 2.0)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ));
+
+        commands.spawn(AudioBundle {
+            source: assets.load("sounds/explosion.wav"),
+            settings: PlaybackSettings {
+                volume: Volume::Relative(VolumeLevel::new(0.8)),
+                ..PlaybackSettings::DESPAWN
+            },
+        });
+    }
+}
+
+pub fn explosion_flash_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut ExplosionFlash, &mut Sprite)>,
+) {
+    for (entity, mut flash, mut sprite) in query.iter_mut() {
+        flash.0.tick(time.delta());
+        sprite.color.set_a(0.8 * flash.0.percent_left());
+        if flash.0.finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
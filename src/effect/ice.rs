@@ -0,0 +1,133 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::block::{Aiming, Block};
+use crate::effect::{effect_texture, EffectDefinition, EffectId, RegisterEffect};
+use crate::level::Level;
+
+pub struct IcePlugin;
+
+impl Plugin for IcePlugin {
+    fn build(&self, app: &mut App) {
+        let effect = IceEffect::default();
+        app.register_effect(
+            EffectDefinition::new(ICE)
+                .with_group("friction")
+                .with_bundle((effect.friction(), effect)),
+        )
+        .add_systems(
+            Update,
+            (freeze_wet_blocks_system, thaw_frozen_blocks_system),
+        );
+    }
+}
+
+pub const ICE: EffectId = EffectId("ice");
+
+pub const FREEZE_SECONDS: f32 = 5.0;
+pub const FROZEN_FRICTION: f32 = 1.5;
+
+/// Almost frictionless block, other blocks slide off of it unless they are nestled in.
+/// On rain levels, wet blocks touching it freeze for a few seconds.
+#[derive(Component, Debug, Clone)]
+pub struct IceEffect {
+    pub friction: f32,
+}
+
+impl Default for IceEffect {
+    fn default() -> Self {
+        Self { friction: 0.02 }
+    }
+}
+
+impl IceEffect {
+    pub fn friction(&self) -> Friction {
+        Friction {
+            coefficient: self.friction,
+            combine_rule: CoefficientCombineRule::Min,
+        }
+    }
+}
+
+/// A wet block that touched ice, it grips everything it touches until it thaws again
+#[derive(Component, Debug)]
+pub struct Frozen {
+    pub timer: Timer,
+    pub previous_friction: Friction,
+    pub overlay: Entity,
+}
+
+pub fn freeze_wet_blocks_system(
+    mut commands: Commands,
+    mut collision_events: EventReader<CollisionEvent>,
+    ice_query: Query<(), (With<IceEffect>, Without<Aiming>)>,
+    block_query: Query<(&Block, &Friction), (Without<IceEffect>, Without<Aiming>)>,
+    mut frozen_query: Query<&mut Frozen>,
+    level: Res<Level>,
+    assets: Res<AssetServer>,
+) {
+    for event in collision_events.read() {
+        if level.rain.is_none() {
+            continue;
+        }
+
+        if let CollisionEvent::Started(a, b, _) = event {
+            [(a, b), (b, a)].into_iter().for_each(|(ice, other)| {
+                if ice_query.get(*ice).is_err() {
+                    return;
+                }
+
+                if let Ok(mut frozen) = frozen_query.get_mut(*other) {
+                    frozen.timer.reset();
+                    return;
+                }
+
+                if let Ok((block, friction)) = block_query.get(*other) {
+                    let overlay = commands
+                        .spawn(SpriteBundle {
+                            transform: Transform::from_xyz(0.0, 0.0, 1.0),
+                            texture: assets.load(effect_texture("ice", block.block_type)),
+                            sprite: Sprite {
+                                custom_size: Some(Vec2::new(
+                                    block.block_type.width(),
+                                    block.block_type.height(),
+                                )),
+                                color: Color::rgba(1.0, 1.0, 1.0, 0.5),
+                                ..Default::default()
+                            },
+                            ..Default::default()
+                        })
+                        .id();
+
+                    commands.entity(*other).add_child(overlay).insert((
+                        Frozen {
+                            timer: Timer::from_seconds(FREEZE_SECONDS, TimerMode::Once),
+                            previous_friction: *friction,
+                            overlay,
+                        },
+                        Friction {
+                            coefficient: FROZEN_FRICTION,
+                            combine_rule: CoefficientCombineRule::Max,
+                        },
+                    ));
+                }
+            });
+        }
+    }
+}
+
+pub fn thaw_frozen_blocks_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Frozen)>,
+) {
+    for (entity, mut frozen) in query.iter_mut() {
+        if frozen.timer.tick(time.delta()).just_finished() {
+            commands.entity(frozen.overlay).despawn_recursive();
+            commands
+                .entity(entity)
+                .insert(frozen.previous_friction)
+                .remove::<Frozen>();
+        }
+    }
+}
@@ -0,0 +1,17 @@
+use bevy::prelude::*;
+
+use crate::effect::{EffectDefinition, EffectId, RegisterEffect};
+
+pub struct LightningRodPlugin;
+
+impl Plugin for LightningRodPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_effect(EffectDefinition::new(LIGHTNING_ROD).with_bundle(LightningRodEffect));
+    }
+}
+
+pub const LIGHTNING_ROD: EffectId = EffectId("lightning_rod");
+
+/// Attracts lightning strikes and grounds them safely, see `environment::lightning`
+#[derive(Component, Debug, Default, Clone)]
+pub struct LightningRodEffect;
@@ -1,21 +1,97 @@
+use std::sync::Arc;
+
 use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use bevy::utils::HashSet;
 use bevy_rapier2d::prelude::*;
 
 use crate::block::{Aiming, Block, Falling};
-use crate::throw::TargetIndicator;
+use crate::effect::{
+    EffectDefinition, EffectId, PredictionHook, RegisterEffect, TrajectoryModifier,
+};
+use crate::level::LevelLifecycle;
+use crate::throw::{TargetIndicator, TargetIndicatorBlock};
 
 pub struct MagneticPlugin;
 
 impl Plugin for MagneticPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(FixedUpdate, magnetic_effect_system);
+        // Both poles share one prediction hook, so the field is only simulated once
+        let prediction: PredictionHook = Arc::new(magnetic_prediction);
+        for polarity in [MagnetPolarity::North, MagnetPolarity::South] {
+            app.register_effect(
+                EffectDefinition::new(polarity.effect())
+                    .with_group("magnet")
+                    .with_bundle(MagneticEffect::new(polarity))
+                    .with_prediction(prediction.clone()),
+            );
+        }
+
+        app.add_systems(FixedUpdate, magnetic_effect_system)
+            .add_systems(
+                Update,
+                (
+                    spawn_magnetic_range_indicator_system,
+                    magnetic_range_indicator_visibility_system,
+                    toggle_magnetic_force_arrows_system,
+                    magnetic_force_arrows_system,
+                ),
+            )
+            .init_resource::<MagneticFieldVisualization>();
     }
 }
 
-#[derive(Component, Debug)]
+pub const MAGNET_NORTH: EffectId = EffectId("magnet/north");
+pub const MAGNET_SOUTH: EffectId = EffectId("magnet/south");
+
+/// Size of the grid cells the force arrows are sampled on
+pub const MAGNETIC_FORCE_ARROW_GRID: f32 = 2.0;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum MagnetPolarity {
+    North,
+    South,
+}
+
+impl MagnetPolarity {
+    pub fn effect(&self) -> EffectId {
+        match self {
+            MagnetPolarity::North => MAGNET_NORTH,
+            MagnetPolarity::South => MAGNET_SOUTH,
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            MagnetPolarity::North => Color::rgb(0.9, 0.2, 0.2),
+            MagnetPolarity::South => Color::rgb(0.2, 0.8, 0.2),
+        }
+    }
+
+    /// 1.0 if the two poles attract each other, -1.0 if they repel each other
+    pub fn interaction(&self, other: MagnetPolarity) -> f32 {
+        if *self == other {
+            -1.0
+        } else {
+            1.0
+        }
+    }
+}
+
+#[derive(Component, Debug, Clone)]
 pub struct MagneticEffect {
     pub range: f32,
     pub force: f32,
+    pub polarity: MagnetPolarity,
+}
+
+impl MagneticEffect {
+    pub fn new(polarity: MagnetPolarity) -> Self {
+        Self {
+            polarity,
+            ..Default::default()
+        }
+    }
 }
 
 impl Default for MagneticEffect {
@@ -23,11 +99,34 @@ impl Default for MagneticEffect {
         Self {
             range: 10.0,
             force: 1.0,
+            polarity: MagnetPolarity::North,
         }
     }
 }
 
-/// Should apply a force to all blocks in range, pulling them towards the center of the block
+#[derive(Resource, Debug)]
+pub struct MagneticFieldVisualization {
+    pub force_arrows: bool,
+}
+
+impl Default for MagneticFieldVisualization {
+    fn default() -> Self {
+        Self { force_arrows: true }
+    }
+}
+
+/// Faint circle around a magnet showing its range, only visible while aiming
+#[derive(Component, Debug)]
+pub struct MagneticRangeIndicator;
+
+#[derive(Component, Debug)]
+pub struct MagneticForceArrow;
+
+#[derive(Component, Debug)]
+pub struct MagneticForceArrowHead;
+
+/// Should apply a force to all blocks in range, pulling them towards the center of the block.
+/// Other magnets are pulled in if their polarity is opposite and pushed away otherwise.
 pub fn magnetic_effect_system(
     mut magnets_query: Query<
         (Entity, &Transform, &mut ExternalImpulse, &MagneticEffect),
@@ -43,14 +142,14 @@ pub fn magnetic_effect_system(
         ),
     >,
 ) {
-    for ((mag_entity, magnet_transform, mut magnet_self_impulse, effect)) in
-        magnets_query.iter_mut()
+    for (_mag_entity, magnet_transform, mut magnet_self_impulse, effect) in magnets_query.iter_mut()
     {
-        for (block_entity, block_transform, mut external_impulse) in blocks_query.iter_mut() {
-            let impulse = calculate_magnetic_impulse(magnet_transform, block_transform, effect);
+        for (_block_entity, block_transform, mut external_impulse) in blocks_query.iter_mut() {
+            let impulse =
+                calculate_magnetic_impulse(magnet_transform, block_transform, effect, None);
 
             if let Some(impulse) = impulse {
-                external_impulse.impulse = impulse;
+                external_impulse.impulse += impulse;
                 magnet_self_impulse.impulse -= impulse;
             }
         }
@@ -58,28 +157,33 @@ pub fn magnetic_effect_system(
 
     let mut combinations = magnets_query.iter_combinations_mut();
     while let Some(
-        [(entity_a, transform_a, mut impulse_a, effect_a), (entity_b, transform_b, mut impulse_b, effect_b)],
+        [(_entity_a, transform_a, mut impulse_a, effect_a), (_entity_b, transform_b, mut impulse_b, effect_b)],
     ) = combinations.fetch_next()
     {
-        let impulse = calculate_magnetic_impulse(transform_a, transform_b, effect_a);
+        let impulse =
+            calculate_magnetic_impulse(transform_a, transform_b, effect_a, Some(effect_b.polarity));
 
         if let Some(impulse) = impulse {
-            impulse_b.impulse = impulse;
+            impulse_b.impulse += impulse;
             impulse_a.impulse -= impulse;
         }
 
-        let impulse = calculate_magnetic_impulse(transform_b, transform_a, effect_b);
+        let impulse =
+            calculate_magnetic_impulse(transform_b, transform_a, effect_b, Some(effect_a.polarity));
         if let Some(impulse) = impulse {
-            impulse_a.impulse = impulse;
+            impulse_a.impulse += impulse;
             impulse_b.impulse -= impulse;
         }
     }
 }
 
+/// Impulse the magnet applies to the block. `block_polarity` is the polarity of the block if it
+/// is a magnet itself, plain blocks are always attracted.
 pub fn calculate_magnetic_impulse(
     magnet_transform: &Transform,
     block_transform: &Transform,
     effect: &MagneticEffect,
+    block_polarity: Option<MagnetPolarity>,
 ) -> Option<Vec2> {
     let distance = magnet_transform
         .translation
@@ -91,9 +195,273 @@ pub fn calculate_magnetic_impulse(
         // max force at 0 distance, 0 force at max distance
         let force = (effect.range - distance) / effect.range * effect.force;
 
+        let force = block_polarity
+            .map(|polarity| force * effect.polarity.interaction(polarity))
+            .unwrap_or(force);
+
         let force = direction * force;
         Some(force)
     } else {
         None
     }
 }
+
+/// Snapshot of the magnetic field around the aimed block
+pub struct MagneticTrajectory {
+    /// Set if the aimed block is a magnet itself
+    pub own: Option<MagneticEffect>,
+    pub magnets: Vec<(Transform, MagneticEffect)>,
+    /// Plain blocks the aimed magnet pulls itself towards
+    pub blocks: Vec<Transform>,
+}
+
+impl TrajectoryModifier for MagneticTrajectory {
+    fn impulse(&self, transform: &Transform) -> Vec2 {
+        let polarity = self.own.as_ref().map(|effect| effect.polarity);
+
+        let mut impulse =
+            self.magnets
+                .iter()
+                .fold(Vec2::ZERO, |acc, (magnet_transform, effect)| {
+                    let mut impulse =
+                        calculate_magnetic_impulse(magnet_transform, transform, effect, polarity)
+                            .unwrap_or(Vec2::ZERO);
+
+                    // Our own field acts on the other magnet, we receive the opposite impulse
+                    if let Some(own) = &self.own {
+                        impulse -= calculate_magnetic_impulse(
+                            transform,
+                            magnet_transform,
+                            own,
+                            Some(effect.polarity),
+                        )
+                        .unwrap_or(Vec2::ZERO);
+                    }
+
+                    impulse + acc
+                });
+
+        if let Some(own) = &self.own {
+            impulse -= self.blocks.iter().fold(Vec2::ZERO, |acc, block_transform| {
+                acc + calculate_magnetic_impulse(transform, block_transform, own, None)
+                    .unwrap_or(Vec2::ZERO)
+            });
+        }
+
+        impulse
+    }
+}
+
+pub fn magnetic_prediction(
+    world: &mut World,
+    aimed: Entity,
+) -> Option<Box<dyn TrajectoryModifier>> {
+    let own = world.get::<MagneticEffect>(aimed).cloned();
+
+    let magnets = world
+        .query_filtered::<(&Transform, &MagneticEffect), (Without<Falling>, Without<Aiming>, With<Block>)>()
+        .iter(world)
+        .map(|(transform, effect)| (*transform, effect.clone()))
+        .collect::<Vec<_>>();
+
+    let blocks = if own.is_some() {
+        world
+            .query_filtered::<&Transform, (
+                With<Block>,
+                Without<MagneticEffect>,
+                Without<Aiming>,
+                Without<TargetIndicator>,
+            )>()
+            .iter(world)
+            .copied()
+            .collect()
+    } else {
+        vec![]
+    };
+
+    if magnets.is_empty() && blocks.is_empty() {
+        return None;
+    }
+
+    Some(Box::new(MagneticTrajectory {
+        own,
+        magnets,
+        blocks,
+    }))
+}
+
+pub fn spawn_magnetic_range_indicator_system(
+    mut commands: Commands,
+    query: Query<(Entity, &MagneticEffect), Added<MagneticEffect>>,
+    assets: Res<AssetServer>,
+) {
+    for (entity, effect) in query.iter() {
+        commands.entity(entity).with_children(|parent| {
+            parent.spawn((
+                MagneticRangeIndicator,
+                SpriteBundle {
+                    transform: Transform::from_xyz(0.0, 0.0, -0.5),
+                    texture: assets.load("circle_outline.png"),
+                    sprite: Sprite {
+                        custom_size: Some(Vec2::splat(effect.range * 2.0)),
+                        color: effect.polarity.color().with_a(0.3),
+                        ..Default::default()
+                    },
+                    visibility: Visibility::Hidden,
+                    ..Default::default()
+                },
+            ));
+        });
+    }
+}
+
+pub fn magnetic_range_indicator_visibility_system(
+    mut query: Query<&mut Visibility, With<MagneticRangeIndicator>>,
+    aiming_query: Query<(), With<Aiming>>,
+    falling_query: Query<(), With<Falling>>,
+) {
+    let is_aiming = !aiming_query.is_empty() && falling_query.is_empty();
+
+    for mut visibility in query.iter_mut() {
+        // Inherited, so the indicator of the aimed block stays hidden with its parent
+        *visibility = if is_aiming {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+pub fn toggle_magnetic_force_arrows_system(
+    key_code: Res<Input<KeyCode>>,
+    mut visualization: ResMut<MagneticFieldVisualization>,
+) {
+    if key_code.just_pressed(KeyCode::M) {
+        visualization.force_arrows = !visualization.force_arrows;
+    }
+}
+
+/// Shows the magnetic force the aimed block would receive, sampled on a grid along the
+/// predicted trajectory. Arrows are reused between frames and hidden when they are not needed.
+pub fn magnetic_force_arrows_system(
+    mut commands: Commands,
+    visualization: Res<MagneticFieldVisualization>,
+    mut arrows: Query<
+        (&mut Transform, &mut Sprite, &mut Visibility, &Children),
+        With<MagneticForceArrow>,
+    >,
+    mut arrow_heads: Query<
+        &mut Transform,
+        (With<MagneticForceArrowHead>, Without<MagneticForceArrow>),
+    >,
+    trajectory_query: Query<
+        &Transform,
+        (
+            With<TargetIndicator>,
+            Without<TargetIndicatorBlock>,
+            Without<MagneticForceArrow>,
+            Without<MagneticForceArrowHead>,
+        ),
+    >,
+    magnets: Query<
+        (&Transform, &MagneticEffect),
+        (
+            Without<Falling>,
+            Without<Aiming>,
+            With<Block>,
+            Without<MagneticForceArrow>,
+            Without<MagneticForceArrowHead>,
+        ),
+    >,
+    aimed_magnet: Query<Option<&MagneticEffect>, With<Aiming>>,
+    assets: Res<AssetServer>,
+) {
+    let mut pool = arrows.iter_mut();
+
+    let aimed = aimed_magnet.get_single();
+    if !visualization.force_arrows || magnets.is_empty() || aimed.is_err() {
+        for (_, _, mut visibility, _) in pool {
+            *visibility = Visibility::Hidden;
+        }
+        return;
+    }
+
+    let polarity = aimed.ok().flatten().map(|effect| effect.polarity);
+
+    let cells = trajectory_query
+        .iter()
+        .map(|transform| {
+            (transform.translation.xy() / MAGNETIC_FORCE_ARROW_GRID)
+                .round()
+                .as_ivec2()
+        })
+        .collect::<HashSet<_>>();
+
+    for cell in cells {
+        let position = cell.as_vec2() * MAGNETIC_FORCE_ARROW_GRID;
+        let probe = Transform::from_translation(Vec3::from((position, 0.0)));
+
+        let impulse = magnets
+            .iter()
+            .fold(Vec2::ZERO, |acc, (magnet_transform, effect)| {
+                acc + calculate_magnetic_impulse(magnet_transform, &probe, effect, polarity)
+                    .unwrap_or(Vec2::ZERO)
+            });
+
+        if impulse.length() < 0.01 {
+            continue;
+        }
+
+        let length = impulse.length().min(1.0) * MAGNETIC_FORCE_ARROW_GRID * 0.75;
+        let transform = Transform::from_xyz(position.x, position.y, 0.5)
+            .with_rotation(Quat::from_rotation_z(Vec2::X.angle_between(impulse)));
+
+        if let Some((mut arrow_transform, mut sprite, mut visibility, children)) = pool.next() {
+            *arrow_transform = transform;
+            sprite.custom_size = Some(Vec2::new(length, 0.06));
+            *visibility = Visibility::Visible;
+            for child in children.iter() {
+                if let Ok(mut head) = arrow_heads.get_mut(*child) {
+                    head.translation.x = length;
+                }
+            }
+            continue;
+        }
+
+        commands
+            .spawn((
+                MagneticForceArrow,
+                LevelLifecycle,
+                SpriteBundle {
+                    transform,
+                    sprite: Sprite {
+                        color: Color::rgba(1.0, 1.0, 1.0, 0.6),
+                        custom_size: Some(Vec2::new(length, 0.06)),
+                        anchor: Anchor::CenterLeft,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    MagneticForceArrowHead,
+                    SpriteBundle {
+                        transform: Transform::from_xyz(length, 0.0, 0.0),
+                        texture: assets.load("circle.png"),
+                        sprite: Sprite {
+                            color: Color::rgba(1.0, 1.0, 1.0, 0.6),
+                            custom_size: Some(Vec2::splat(0.2)),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    },
+                ));
+            });
+    }
+
+    // Arrows left over from a longer trajectory
+    for (_, _, mut visibility, _) in pool {
+        *visibility = Visibility::Hidden;
+    }
+}
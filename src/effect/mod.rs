@@ -1,88 +1,294 @@
+use std::sync::Arc;
+
+use bevy::ecs::system::Command;
 use bevy::prelude::*;
+use bevy::utils::HashMap;
+use bevy_rapier2d::prelude::{Toi, Velocity};
 
-use crate::block::BlockType;
-use crate::effect::glue::GlueEffect;
-use crate::effect::platform::PlatformEffect;
+use crate::block::{Aiming, Block, BlockType, CaughtBlock};
 
+pub mod anchor;
+pub mod balloon;
+pub mod explosive;
 pub mod glue;
+pub mod ice;
+pub mod lightning_rod;
 pub mod magnetic;
 pub mod platform;
+pub mod rubber;
 
 pub struct EffectPlugin;
 
 impl Plugin for EffectPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((
-            glue::GluePlugin,
-            platform::PlatformEffectPlugin,
-            magnetic::MagneticPlugin,
-        ));
+        app.init_resource::<EffectRegistry>()
+            .init_resource::<TrajectoryModifiers>()
+            .add_systems(Update, effect_catch_hook_system)
+            .add_plugins((
+                glue::GluePlugin,
+                platform::PlatformEffectPlugin,
+                magnetic::MagneticPlugin,
+                balloon::BalloonPlugin,
+                explosive::ExplosivePlugin,
+                anchor::AnchorPlugin,
+                ice::IcePlugin,
+                rubber::RubberPlugin,
+                lightning_rod::LightningRodPlugin,
+            ));
     }
 }
 
-#[derive(Debug, Clone, Copy)]
-pub enum EffectType {
-    Glue,
-    Platform,
-    Magnetic,
-}
+/// Identifies an effect in the [`EffectRegistry`], levels and blocks only refer to effects by id
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct EffectId(pub &'static str);
 
-pub const ALL_EFFECTS: [EffectType; 3] =
-    [EffectType::Glue, EffectType::Platform, EffectType::Magnetic];
+/// Runs with exclusive world access for a single block
+pub type EffectHook = Arc<dyn Fn(&mut World, Entity) + Send + Sync>;
 
-pub const DEFAULT_EFFECTS: [EffectType; 2] = [EffectType::Glue, EffectType::Magnetic];
+/// Called once per frame with the aimed block, returns a modifier if the effect changes the
+/// predicted trajectory of that block
+pub type PredictionHook =
+    Arc<dyn Fn(&mut World, Entity) -> Option<Box<dyn TrajectoryModifier>> + Send + Sync>;
 
-pub fn glue_texture(block_type: BlockType) -> String {
-    format!("effects/glue/{}.png", block_type.letter().to_lowercase())
+/// Influences the trajectory preview of the aimed block, see `simulate_throw_system`
+pub trait TrajectoryModifier: Send + Sync {
+    /// Constant acceleration added on top of gravity
+    fn acceleration(&self) -> Vec2 {
+        Vec2::ZERO
+    }
+
+    /// Impulse applied to the block in every simulation step
+    fn impulse(&self, _transform: &Transform) -> Vec2 {
+        Vec2::ZERO
+    }
+
+    /// Called when the predicted trajectory hits something.
+    /// Returns true if the block should keep moving with the updated velocity.
+    fn on_hit(&mut self, _velocity: &mut Velocity, _toi: &Toi) -> bool {
+        false
+    }
 }
 
-impl EffectType {
-    pub fn texture(&self, block_type: BlockType) -> String {
-        match self {
-            EffectType::Glue => glue_texture(block_type),
-            EffectType::Platform => "fixed.png".to_string(),
-            EffectType::Magnetic => {
-                format!("effects/magnet/{}.png", block_type.letter().to_lowercase())
-            }
+/// Everything the game needs to know about an effect. Effect plugins register their definition
+/// with [`RegisterEffect::register_effect`]. Effects that only change physics properties can be
+/// defined from data alone with [`EffectDefinition::with_bundle`].
+#[derive(Clone)]
+pub struct EffectDefinition {
+    pub id: EffectId,
+    /// Folder in `assets/effects` containing one overlay per block type
+    pub texture_folder: &'static str,
+    /// Effects of the same group can't be combined on one block
+    pub group: Option<&'static str>,
+    pub on_spawn: Option<EffectHook>,
+    pub on_catch: Option<EffectHook>,
+    pub prediction: Option<PredictionHook>,
+}
+
+impl EffectDefinition {
+    pub fn new(id: EffectId) -> Self {
+        Self {
+            id,
+            texture_folder: id.0,
+            group: None,
+            on_spawn: None,
+            on_catch: None,
+            prediction: None,
         }
     }
 
-    fn insert_effect(&self, commands: &mut Commands, entity: Entity) {
-        match self {
-            EffectType::Glue => {
-                commands.entity(entity).insert(GlueEffect::default());
-            }
-            EffectType::Platform => {
-                commands.entity(entity).insert(PlatformEffect::default());
-            }
-            EffectType::Magnetic => {
-                commands
-                    .entity(entity)
-                    .insert(magnetic::MagneticEffect::default());
-            }
+    pub fn with_group(mut self, group: &'static str) -> Self {
+        self.group = Some(group);
+        self
+    }
+
+    pub fn on_spawn(mut self, hook: impl Fn(&mut World, Entity) + Send + Sync + 'static) -> Self {
+        self.on_spawn = Some(Arc::new(hook));
+        self
+    }
+
+    pub fn on_catch(mut self, hook: impl Fn(&mut World, Entity) + Send + Sync + 'static) -> Self {
+        self.on_catch = Some(Arc::new(hook));
+        self
+    }
+
+    pub fn with_prediction(mut self, hook: PredictionHook) -> Self {
+        self.prediction = Some(hook);
+        self
+    }
+
+    /// Inserts a copy of the bundle when a block with this effect is spawned
+    pub fn with_bundle(self, bundle: impl Bundle + Clone) -> Self {
+        self.on_spawn(move |world, entity| {
+            world.entity_mut(entity).insert(bundle.clone());
+        })
+    }
+
+    pub fn texture(&self, block_type: BlockType) -> String {
+        effect_texture(self.texture_folder, block_type)
+    }
+
+    pub fn conflicts_with(&self, other: &EffectDefinition) -> bool {
+        self.id == other.id || (self.group.is_some() && self.group == other.group)
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct EffectRegistry {
+    effects: HashMap<EffectId, EffectDefinition>,
+}
+
+impl EffectRegistry {
+    pub fn register(&mut self, definition: EffectDefinition) {
+        self.effects.insert(definition.id, definition);
+    }
+
+    pub fn get(&self, id: EffectId) -> Option<&EffectDefinition> {
+        self.effects.get(&id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &EffectDefinition> {
+        self.effects.values()
+    }
+
+    /// Unknown effects never conflict, they are skipped when the block is spawned anyway
+    pub fn conflicts(&self, a: EffectId, b: EffectId) -> bool {
+        match (self.get(a), self.get(b)) {
+            (Some(a), Some(b)) => a.conflicts_with(b),
+            _ => a == b,
         }
     }
+}
+
+pub trait RegisterEffect {
+    fn register_effect(&mut self, definition: EffectDefinition) -> &mut Self;
+}
+
+impl RegisterEffect for App {
+    fn register_effect(&mut self, definition: EffectDefinition) -> &mut Self {
+        self.init_resource::<EffectRegistry>();
+        self.world
+            .resource_mut::<EffectRegistry>()
+            .register(definition);
+        self
+    }
+}
 
-    pub fn enable(
-        &self,
-        commands: &mut Commands,
-        assets: &AssetServer,
-        entity: Entity,
-        block_type: BlockType,
-    ) {
-        self.insert_effect(commands, entity);
-        let texture = self.texture(block_type);
-        commands.entity(entity).with_children(|parent| {
-            parent.spawn(SpriteBundle {
-                transform: Transform::from_xyz(0.0, 0.0, 1.0),
-                texture: assets.load(texture),
+/// Prediction modifiers of the currently aimed block, rebuilt every frame
+#[derive(Resource, Default)]
+pub struct TrajectoryModifiers(pub Vec<Box<dyn TrajectoryModifier>>);
+
+pub fn effect_texture(folder: &str, block_type: BlockType) -> String {
+    format!(
+        "effects/{}/{}.png",
+        folder,
+        block_type.letter().to_lowercase()
+    )
+}
+
+pub fn glue_texture(block_type: BlockType) -> String {
+    effect_texture("glue", block_type)
+}
+
+/// Runs the spawn hook of an effect and adds its overlay to the block
+pub struct EnableEffect {
+    pub entity: Entity,
+    pub effect: EffectId,
+    pub block_type: BlockType,
+    /// Overlays of multiple effects are stacked on top of each other
+    pub layer: usize,
+}
+
+impl Command for EnableEffect {
+    fn apply(self, world: &mut World) {
+        if world.get_entity(self.entity).is_none() {
+            return;
+        }
+
+        let Some((on_spawn, texture)) =
+            world
+                .resource::<EffectRegistry>()
+                .get(self.effect)
+                .map(|definition| {
+                    (
+                        definition.on_spawn.clone(),
+                        definition.texture(self.block_type),
+                    )
+                })
+        else {
+            warn!("Unknown effect {:?}", self.effect);
+            return;
+        };
+
+        if let Some(on_spawn) = on_spawn {
+            on_spawn(world, self.entity);
+        }
+
+        let texture = world.resource::<AssetServer>().load(texture);
+        let overlay = world
+            .spawn(SpriteBundle {
+                transform: Transform::from_xyz(0.0, 0.0, 1.0 + self.layer as f32 * 0.1),
+                texture,
                 sprite: Sprite {
-                    custom_size: Some(Vec2::new(block_type.width(), block_type.height())),
+                    custom_size: Some(Vec2::new(self.block_type.width(), self.block_type.height())),
                     color: Color::rgba(1.0, 1.0, 1.0, 0.75),
                     ..Default::default()
                 },
                 ..Default::default()
-            });
-        });
+            })
+            .id();
+        world.entity_mut(self.entity).add_child(overlay);
     }
 }
+
+pub fn effect_catch_hook_system(
+    mut commands: Commands,
+    mut events: EventReader<CaughtBlock>,
+    query: Query<&Block>,
+    registry: Res<EffectRegistry>,
+) {
+    for event in events.read() {
+        let Ok(block) = query.get(event.entity) else {
+            continue;
+        };
+
+        for effect in &block.effects {
+            if let Some(hook) = registry
+                .get(*effect)
+                .and_then(|definition| definition.on_catch.clone())
+            {
+                let entity = event.entity;
+                commands.add(move |world: &mut World| {
+                    if world.get_entity(entity).is_some() {
+                        hook(world, entity);
+                    }
+                });
+            }
+        }
+    }
+}
+
+/// Collects the trajectory modifiers for the aimed block.
+/// Hooks shared between several effects only run once.
+pub fn prepare_trajectory_modifiers_system(world: &mut World) {
+    let aimed = world
+        .query_filtered::<Entity, With<Aiming>>()
+        .iter(world)
+        .next();
+
+    let mut hooks: Vec<PredictionHook> = vec![];
+    for hook in world
+        .resource::<EffectRegistry>()
+        .iter()
+        .filter_map(|definition| definition.prediction.clone())
+    {
+        if !hooks.iter().any(|other| Arc::ptr_eq(other, &hook)) {
+            hooks.push(hook);
+        }
+    }
+
+    let modifiers = aimed
+        .map(|aimed| hooks.iter().filter_map(|hook| hook(world, aimed)).collect())
+        .unwrap_or_default();
+
+    world.resource_mut::<TrajectoryModifiers>().0 = modifiers;
+}
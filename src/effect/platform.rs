@@ -1,6 +1,6 @@
 use crate::base::Base;
-use crate::block::{CaughtBlock, Falling};
-use crate::effect::magnetic::MagneticEffect;
+use crate::block::Falling;
+use crate::effect::{EffectDefinition, EffectId, RegisterEffect};
 use bevy::prelude::*;
 use bevy_rapier2d::prelude::*;
 
@@ -8,33 +8,67 @@ pub struct PlatformEffectPlugin;
 
 impl Plugin for PlatformEffectPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, platform_remove_physics_system);
+        app.register_effect(
+            EffectDefinition::new(PLATFORM)
+                .with_bundle(PlatformEffect::default())
+                .on_catch(platform_remove_physics),
+        )
+        .add_systems(Update, platform_effect_system);
     }
 }
 
+pub const PLATFORM: EffectId = EffectId("platform");
+
 /// Causes the entity to move left and right in sync with the base platform
 /// Helps stabilize the pile
-#[derive(Component, Debug, Default)]
-pub struct PlatformEffect;
+#[derive(Component, Debug, Default, Clone)]
+pub struct PlatformEffect {
+    /// The base the block is locked to, set once the block is caught
+    pub base: Option<Entity>,
+}
+
+/// Locks the block to the base closest to it, it won't be moved by physics anymore
+pub fn platform_remove_physics(world: &mut World, entity: Entity) {
+    let Some(position) = world.get::<Transform>(entity).map(|t| t.translation.xy()) else {
+        return;
+    };
+
+    let base = world
+        .query_filtered::<(Entity, &Transform), With<Base>>()
+        .iter(world)
+        .min_by(|(_, a), (_, b)| {
+            let a = (a.translation.x - position.x).abs();
+            let b = (b.translation.x - position.x).abs();
+            a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .map(|(base, _)| base);
 
-pub fn platform_remove_physics_system(
-    mut events: EventReader<CaughtBlock>,
-    mut commands: Commands,
-    mut query: Query<Entity, With<PlatformEffect>>,
+    if let Some(base) = base {
+        world.entity_mut(entity).insert((
+            RigidBody::KinematicVelocityBased,
+            PlatformEffect { base: Some(base) },
+        ));
+    } else {
+        world.entity_mut(entity).insert(RigidBody::Fixed);
+    }
+}
+
+/// Moves the block with the point of the base below it. The block itself doesn't rotate, so it
+/// stays upright on seesaws and turntables.
+pub fn platform_effect_system(
+    mut query: Query<
+        (&mut Velocity, &Transform, &PlatformEffect),
+        (Without<Base>, Without<Falling>),
+    >,
+    base_query: Query<(&Velocity, &Transform), With<Base>>,
 ) {
-    for event in events.read() {
-        if let Ok(entity) = query.get_mut(event.entity) {
-            commands.entity(entity).insert(RigidBody::Fixed);
+    for (mut velocity, transform, effect) in query.iter_mut() {
+        if let Some((base_velocity, base_transform)) =
+            effect.base.and_then(|base| base_query.get(base).ok())
+        {
+            let offset = transform.translation.xy() - base_transform.translation.xy();
+            velocity.linvel = base_velocity.linvel + base_velocity.angvel * offset.perp();
+            velocity.angvel = 0.0;
         }
     }
 }
-
-// pub fn platform_effect_system(
-//     mut query: Query<(&mut Velocity), (With<PlatformEffect>, Without<Base>, Without<Falling>)>,
-//     base_query: Query<&Velocity, With<Base>>,
-// ) {
-//     let base_velocity = base_query.single();
-//     for (mut velocity) in query.iter_mut() {
-//         *velocity = base_velocity.clone();
-//     }
-// }
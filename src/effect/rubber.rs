@@ -0,0 +1,68 @@
+use std::sync::Arc;
+
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::effect::{EffectDefinition, EffectId, RegisterEffect, TrajectoryModifier};
+
+pub struct RubberPlugin;
+
+impl Plugin for RubberPlugin {
+    fn build(&self, app: &mut App) {
+        let effect = RubberEffect::default();
+        app.register_effect(
+            EffectDefinition::new(RUBBER)
+                .with_bundle((effect.restitution(), effect))
+                .with_prediction(Arc::new(rubber_prediction)),
+        );
+    }
+}
+
+pub const RUBBER: EffectId = EffectId("rubber");
+
+/// Bouncy block that springs back off whatever it lands on
+#[derive(Component, Debug, Clone)]
+pub struct RubberEffect {
+    pub restitution: f32,
+}
+
+impl Default for RubberEffect {
+    fn default() -> Self {
+        Self { restitution: 0.8 }
+    }
+}
+
+impl RubberEffect {
+    pub fn restitution(&self) -> Restitution {
+        Restitution {
+            coefficient: self.restitution,
+            combine_rule: CoefficientCombineRule::Max,
+        }
+    }
+}
+
+/// Rubber blocks bounce off the first thing they hit, so we show that bounce as well
+pub struct RubberBounce {
+    pub restitution: Option<f32>,
+}
+
+impl TrajectoryModifier for RubberBounce {
+    fn on_hit(&mut self, velocity: &mut Velocity, toi: &Toi) -> bool {
+        // There is no contact normal when the shapes already overlap, so there is nothing to bounce off
+        if let (Some(restitution), Some(details)) = (self.restitution.take(), toi.details) {
+            let normal = details.normal1;
+            velocity.linvel -= (1.0 + restitution) * velocity.linvel.dot(normal) * normal;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+pub fn rubber_prediction(world: &mut World, aimed: Entity) -> Option<Box<dyn TrajectoryModifier>> {
+    world.get::<RubberEffect>(aimed).map(|effect| {
+        Box::new(RubberBounce {
+            restitution: Some(effect.restitution),
+        }) as Box<dyn TrajectoryModifier>
+    })
+}
@@ -18,6 +18,7 @@ pub struct Beam {
 }
 
 #[derive(Bundle, Debug)]
+#[allow(dead_code)]
 pub struct BeamBundle {
     pub beam: Beam,
     pub source_transform: SpatialBundle,
@@ -57,7 +58,7 @@ pub fn update_beam_system(
     mut query: Query<(Entity, &Beam, &mut Transform, &GlobalTransform, &mut Sprite)>,
     target_query: Query<&GlobalTransform>,
 ) {
-    for (beam_entity, beam, mut beam_transform, beam_global_transform, mut sprite) in
+    for (_beam_entity, beam, mut beam_transform, beam_global_transform, mut sprite) in
         query.iter_mut()
     {
         if let Ok(target_global_transform) = target_query.get(beam.target) {
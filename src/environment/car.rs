@@ -106,6 +106,7 @@ impl CarType {
 #[derive(Component, Debug)]
 pub struct Car {
     state: CarState,
+    #[allow(dead_code)]
     car_type: CarType,
 }
 
@@ -229,8 +230,8 @@ fn car_collision_system(
     mut commands: Commands,
     mut collision_events: EventReader<CollisionEvent>,
     mut car_query: Query<(Entity, &mut Car, &Children)>,
-    mut car_sprite_query: Query<(&CarTexture)>,
-    mut block_query: Query<Entity, With<Block>>,
+    mut car_sprite_query: Query<&CarTexture>,
+    block_query: Query<Entity, With<Block>>,
     mut car_crashed_events: EventWriter<CarCrashedEvent>,
     mut update_level_stats_events: EventWriter<UpdateLevelStats>,
 ) {
@@ -240,7 +241,7 @@ fn car_collision_system(
                 [(a, b), (b, a)]
                     .into_iter()
                     .for_each(|(car_entity, block_entity)| {
-                        if !block_query.get(*block_entity).is_ok() {
+                        if block_query.get(*block_entity).is_err() {
                             return;
                         }
                         if let Ok((car_entity, mut car, children)) = car_query.get_mut(*car_entity)
@@ -259,7 +260,7 @@ fn car_collision_system(
                             update_level_stats_events.send(UpdateLevelStats::CarHit);
 
                             for child in children.iter() {
-                                if let Ok((texture)) = car_sprite_query.get_mut(*child) {
+                                if let Ok(texture) = car_sprite_query.get_mut(*child) {
                                     if texture.on {
                                         commands.entity(*child).insert(Visibility::Hidden);
                                     } else {
@@ -278,7 +279,7 @@ fn car_collision_system(
 pub fn car_remove_debris_collision_timer_system(
     mut commands: Commands,
     mut car_query: Query<(Entity, &mut Car)>,
-    mut time: ResMut<Time>,
+    time: ResMut<Time>,
 ) {
     for (entity, mut car) in car_query.iter_mut() {
         if let CarState::Crashed {
@@ -287,12 +288,10 @@ pub fn car_remove_debris_collision_timer_system(
         {
             remove_debris_collision_timer.tick(time.delta());
             if remove_debris_collision_timer.just_finished() {
-                commands.entity(entity).insert(
-                    (CollisionGroups {
-                        filters: FLOOR_COLLISION_GROUP | BLOCK_COLLISION_GROUP,
-                        memberships: Group::default(),
-                    }),
-                );
+                commands.entity(entity).insert(CollisionGroups {
+                    filters: FLOOR_COLLISION_GROUP | BLOCK_COLLISION_GROUP,
+                    memberships: Group::default(),
+                });
             }
         }
     }
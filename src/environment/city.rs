@@ -3,7 +3,6 @@ use bevy::sprite::Anchor;
 
 use crate::camera_movement::CameraMovement;
 use crate::environment::rain::DarkenSpriteOnRain;
-use crate::level::Level;
 use crate::state::LevelState;
 use crate::MainCamera;
 
@@ -23,7 +22,7 @@ pub struct AutoWidth {
     parallax: f32,
 }
 
-pub fn setup_city(mut commands: Commands, assets: Res<AssetServer>, level: Res<Level>) {
+pub fn setup_city(mut commands: Commands, assets: Res<AssetServer>) {
     let mut spawn = |width: f32, height: f32, z: f32, parallax: f32, open_top, name| {
         let aspect_ratio = height / width;
         commands.spawn((
@@ -32,7 +32,7 @@ pub fn spawn_debris_cleaner(
     mut commands: Commands,
     mut debris_query: Query<Entity, With<Debris>>,
     mut debris_cleaner_query: Query<Entity, With<DebrisCleaner>>,
-    mut assets: ResMut<AssetServer>,
+    assets: ResMut<AssetServer>,
 ) {
     let res_w = 1395.0;
     let res_h = 671.0;
@@ -41,7 +41,7 @@ pub fn spawn_debris_cleaner(
 
     let count = debris_query.iter_mut().count();
 
-    let mut debris_cleaner_count = debris_cleaner_query.iter_mut().count();
+    let debris_cleaner_count = debris_cleaner_query.iter_mut().count();
 
     if count > DEBRIS_CLEANER_THRESHOLD && debris_cleaner_count == 0 {
         commands.spawn((
@@ -69,12 +69,11 @@ pub fn spawn_debris_cleaner(
 
 // The debris cleaner attracts all debris within it's range and despawns it on collision
 pub fn debris_cleaner_attraction(
-    mut commands: Commands,
     mut debris_cleaner_query: Query<(Entity, &Transform), With<DebrisCleaner>>,
     mut debris_query: Query<(Entity, &Transform, &mut ExternalImpulse), With<Debris>>,
 ) {
-    for (debris_cleaner_entity, debris_cleaner_transform) in debris_cleaner_query.iter_mut() {
-        for (debris_entity, debris_transform, mut debris_impulse) in debris_query.iter_mut() {
+    for (_debris_cleaner_entity, debris_cleaner_transform) in debris_cleaner_query.iter_mut() {
+        for (_debris_entity, debris_transform, mut debris_impulse) in debris_query.iter_mut() {
             let distance = debris_transform
                 .translation
                 .distance(debris_cleaner_transform.translation);
@@ -94,36 +93,31 @@ pub fn debris_cleaner_attraction(
 
 pub fn debris_cleaner_collision(
     mut commands: Commands,
-    mut debris_cleaner_query: Query<Entity, With<DebrisCleaner>>,
+    debris_cleaner_query: Query<Entity, With<DebrisCleaner>>,
     mut debris_query: Query<Entity, With<Debris>>,
     mut debris_cleaner_collision_events: EventReader<CollisionEvent>,
-    mut assets: ResMut<AssetServer>,
+    assets: ResMut<AssetServer>,
 ) {
-    for event in debris_cleaner_collision_events.iter() {
-        match event {
-            CollisionEvent::Started(a, b, _) => {
-                [(a, b), (b, a)]
-                    .into_iter()
-                    .for_each(|(debris_cleaner, debris)| {
-                        if debris_cleaner_query.get(*debris_cleaner).is_err() {
-                            return;
-                        }
-
-                        if let Ok(debris) = debris_query.get_mut(*debris) {
-                            commands.entity(debris).despawn_recursive();
-                            commands.spawn(
-                                (AudioBundle {
-                                    source: assets.load("sounds/whoosh.wav"),
-                                    settings: PlaybackSettings {
-                                        volume: Volume::Relative(VolumeLevel::new(0.5)),
-                                        ..PlaybackSettings::DESPAWN
-                                    },
-                                }),
-                            );
-                        }
-                    });
-            }
-            _ => {}
+    for event in debris_cleaner_collision_events.read() {
+        if let CollisionEvent::Started(a, b, _) = event {
+            [(a, b), (b, a)]
+                .into_iter()
+                .for_each(|(debris_cleaner, debris)| {
+                    if debris_cleaner_query.get(*debris_cleaner).is_err() {
+                        return;
+                    }
+
+                    if let Ok(debris) = debris_query.get_mut(*debris) {
+                        commands.entity(debris).despawn_recursive();
+                        commands.spawn(AudioBundle {
+                            source: assets.load("sounds/whoosh.wav"),
+                            settings: PlaybackSettings {
+                                volume: Volume::Relative(VolumeLevel::new(0.5)),
+                                ..PlaybackSettings::DESPAWN
+                            },
+                        });
+                    }
+                });
         }
     }
 }
@@ -0,0 +1,173 @@
+use std::f32::consts::{PI, TAU};
+
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::base::{keyboard_base_movement_system, path_base_movement_system, Base, BasePivot};
+use crate::camera_movement::CameraMovement;
+use crate::level::{Level, LevelLifecycle, LevelStats};
+use crate::state::LevelState;
+
+pub struct EarthquakePlugin;
+
+impl Plugin for EarthquakePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(LevelState::Playing), reset_earthquakes)
+            .add_systems(OnExit(LevelState::Playing), stop_earthquake)
+            .add_systems(OnExit(LevelState::KeepPlaying), stop_earthquake)
+            .add_systems(
+                Update,
+                (
+                    undo_base_shake_system
+                        .before(keyboard_base_movement_system)
+                        .before(path_base_movement_system),
+                    earthquake_system
+                        .after(undo_base_shake_system)
+                        .after(keyboard_base_movement_system)
+                        .after(path_base_movement_system)
+                        .run_if(
+                            in_state(LevelState::Playing)
+                                .or_else(in_state(LevelState::KeepPlaying)),
+                        ),
+                ),
+            )
+            .init_resource::<EarthquakeState>();
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum EarthquakeTrigger {
+    /// Seconds after the level started
+    AfterSeconds(f32),
+    /// As soon as the tower reaches this height
+    AtHeight(f32),
+}
+
+/// Shakes all bases horizontally, `amplitude` is the maximum displacement in meters
+#[derive(Debug, Clone)]
+pub struct Earthquake {
+    pub trigger: EarthquakeTrigger,
+    pub amplitude: f32,
+    pub frequency: f32,
+    pub duration: f32,
+}
+
+#[derive(Debug)]
+pub struct ActiveEarthquake {
+    pub index: usize,
+    pub elapsed: f32,
+    pub rumble: Entity,
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct EarthquakeState {
+    pub elapsed: f32,
+    /// Index of the next earthquake in `Level::earthquakes`
+    pub next: usize,
+    pub active: Option<ActiveEarthquake>,
+    /// Velocity added to the bases last frame, removed again before the bases move
+    pub applied_velocity: f32,
+}
+
+#[derive(Component, Debug)]
+pub struct EarthquakeRumble;
+
+pub fn reset_earthquakes(mut state: ResMut<EarthquakeState>) {
+    *state = EarthquakeState::default();
+}
+
+pub fn stop_earthquake(mut commands: Commands, mut state: ResMut<EarthquakeState>) {
+    if let Some(active) = state.active.take() {
+        commands.entity(active.rumble).despawn_recursive();
+    }
+}
+
+/// Runs every frame, so the bases stop shaking as soon as the earthquake system stops
+pub fn undo_base_shake_system(
+    mut state: ResMut<EarthquakeState>,
+    mut query: Query<(&mut Velocity, &RigidBody), Or<(With<Base>, With<BasePivot>)>>,
+    mut camera_movement: ResMut<CameraMovement>,
+) {
+    for (mut velocity, _) in query
+        .iter_mut()
+        .filter(|(_, body)| **body == RigidBody::KinematicVelocityBased)
+    {
+        velocity.linvel.x -= state.applied_velocity;
+    }
+    state.applied_velocity = 0.0;
+    camera_movement.shake = 0.0;
+}
+
+pub fn earthquake_system(
+    mut commands: Commands,
+    mut state: ResMut<EarthquakeState>,
+    mut query: Query<(&mut Velocity, &RigidBody), Or<(With<Base>, With<BasePivot>)>>,
+    mut camera_movement: ResMut<CameraMovement>,
+    level: Res<Level>,
+    level_stats: Res<LevelStats>,
+    time: Res<Time>,
+    assets: Res<AssetServer>,
+) {
+    state.elapsed += time.delta_seconds();
+
+    if state.active.is_none() {
+        if let Some(earthquake) = level.earthquakes.get(state.next) {
+            let triggered = match earthquake.trigger {
+                EarthquakeTrigger::AfterSeconds(seconds) => state.elapsed >= seconds,
+                EarthquakeTrigger::AtHeight(height) => level_stats.current_height >= height,
+            };
+
+            if triggered {
+                let rumble = commands
+                    .spawn((
+                        EarthquakeRumble,
+                        LevelLifecycle,
+                        AudioBundle {
+                            source: assets.load("sounds/rumble.wav"),
+                            settings: PlaybackSettings::LOOP,
+                        },
+                    ))
+                    .id();
+
+                state.active = Some(ActiveEarthquake {
+                    index: state.next,
+                    elapsed: 0.0,
+                    rumble,
+                });
+                state.next += 1;
+            }
+        }
+    }
+
+    let Some(active) = &mut state.active else {
+        return;
+    };
+    let earthquake = &level.earthquakes[active.index];
+
+    active.elapsed += time.delta_seconds();
+
+    if active.elapsed >= earthquake.duration {
+        commands.entity(active.rumble).despawn_recursive();
+        state.active = None;
+        return;
+    }
+
+    // Ramp the quake up and down again instead of starting and stopping abruptly
+    let envelope = (active.elapsed / earthquake.duration * PI).sin();
+    let angular_frequency = earthquake.frequency * TAU;
+    let shake_velocity = earthquake.amplitude
+        * envelope
+        * angular_frequency
+        * (active.elapsed * angular_frequency).cos();
+
+    // Mounted bases are dynamic, they are shaken through their kinematic pivot instead
+    for (mut velocity, _) in query
+        .iter_mut()
+        .filter(|(_, body)| **body == RigidBody::KinematicVelocityBased)
+    {
+        velocity.linvel.x += shake_velocity;
+    }
+
+    camera_movement.shake = earthquake.amplitude * envelope * 0.3;
+    state.applied_velocity = shake_velocity;
+}
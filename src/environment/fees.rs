@@ -1,7 +1,6 @@
-use crate::level::LevelStats;
+use crate::level::{Level, LevelStats};
 use crate::state::LevelState;
 use bevy::prelude::*;
-use bevy_rapier2d::prelude::*;
 
 pub struct FeesPlugin;
 
@@ -17,11 +16,15 @@ impl Plugin for FeesPlugin {
 pub struct LevelFees {
     pub cleanup_fee: f32,
     pub property_damage: f32,
+    pub demolition_fee: f32,
+    pub fuel_fee: f32,
 }
 
-pub fn update_fees(mut level_fees: ResMut<LevelFees>, stats: Res<LevelStats>) {
+pub fn update_fees(mut level_fees: ResMut<LevelFees>, stats: Res<LevelStats>, level: Res<Level>) {
     level_fees.cleanup_fee = stats.blocks_dropped as f32 * 100.0;
     level_fees.property_damage = stats.cars_hit as f32 * 10000.0;
+    level_fees.demolition_fee = stats.blocks_demolished as f32 * 250.0;
+    level_fees.fuel_fee = stats.fuel_used * level.fuel.as_ref().map_or(0.0, |fuel| fuel.price);
 }
 
 pub fn reset_level_fees(mut level_fees: ResMut<LevelFees>) {
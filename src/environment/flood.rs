@@ -0,0 +1,130 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::block::{Block, DestroyBlockOnContact};
+use crate::consts::{BLOCK_COLLISION_GROUP, FLOOR_COLLISION_GROUP};
+use crate::environment::rain::spawn_splash;
+use crate::level::{Level, LevelLifecycle};
+use crate::state::LevelState;
+use crate::HORIZONTAL_VIEWPORT_SIZE;
+
+pub struct FloodPlugin;
+
+impl Plugin for FloodPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(LevelState::Playing), setup_flood)
+            .add_systems(
+                Update,
+                (rising_water_system, water_splash_system).run_if(
+                    in_state(LevelState::Playing).or_else(in_state(LevelState::KeepPlaying)),
+                ),
+            );
+    }
+}
+
+/// Depth of the water body below its surface. Keeps the center of the water above the height
+/// where `despawn_lost_entities` removes entities.
+pub const WATER_DEPTH: f32 = 20.0;
+
+/// Water that starts rising after `delay` seconds and destroys every block it touches
+#[derive(Debug, Clone)]
+pub struct Flood {
+    pub start_height: f32,
+    pub max_height: f32,
+    /// Meters per second
+    pub rise_speed: f32,
+    pub delay: f32,
+}
+
+#[derive(Component, Debug)]
+pub struct Water {
+    pub delay: Timer,
+}
+
+impl Water {
+    pub fn surface(transform: &Transform) -> f32 {
+        transform.translation.y + WATER_DEPTH / 2.0
+    }
+}
+
+pub fn setup_flood(mut commands: Commands, level: Res<Level>) {
+    let Some(flood) = &level.flood else {
+        return;
+    };
+
+    let width = HORIZONTAL_VIEWPORT_SIZE * 2.0;
+
+    commands.spawn((
+        Water {
+            delay: Timer::from_seconds(flood.delay, TimerMode::Once),
+        },
+        LevelLifecycle,
+        DestroyBlockOnContact,
+        SpriteBundle {
+            transform: Transform::from_xyz(0.0, flood.start_height - WATER_DEPTH / 2.0, 2.0),
+            sprite: Sprite {
+                color: Color::rgba(0.2, 0.45, 0.8, 0.5),
+                custom_size: Some(Vec2::new(width, WATER_DEPTH)),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        RigidBody::KinematicVelocityBased,
+        Velocity::zero(),
+        Collider::cuboid(width / 2.0, WATER_DEPTH / 2.0),
+        Sensor,
+        // Platform blocks are kinematic once they are caught
+        ActiveCollisionTypes::default() | ActiveCollisionTypes::KINEMATIC_KINEMATIC,
+        CollisionGroups {
+            memberships: FLOOR_COLLISION_GROUP,
+            filters: BLOCK_COLLISION_GROUP,
+        },
+    ));
+}
+
+pub fn rising_water_system(
+    mut query: Query<(&mut Water, &Transform, &mut Velocity)>,
+    level: Res<Level>,
+    time: Res<Time>,
+) {
+    let Some(flood) = &level.flood else {
+        return;
+    };
+
+    for (mut water, transform, mut velocity) in query.iter_mut() {
+        let rising = water.delay.tick(time.delta()).finished()
+            && Water::surface(transform) < flood.max_height;
+
+        velocity.linvel.y = if rising { flood.rise_speed } else { 0.0 };
+    }
+}
+
+pub fn water_splash_system(
+    mut commands: Commands,
+    mut collision_events: EventReader<CollisionEvent>,
+    water_query: Query<&Transform, With<Water>>,
+    block_query: Query<(&Block, &Transform)>,
+) {
+    for event in collision_events.read() {
+        if let CollisionEvent::Started(a, b, _) = event {
+            [(a, b), (b, a)].into_iter().for_each(|(water, block)| {
+                let Ok(water_transform) = water_query.get(*water) else {
+                    return;
+                };
+
+                if let Ok((block, transform)) = block_query.get(*block) {
+                    let surface = Water::surface(water_transform);
+                    let half_width = block.block_type.width() / 2.0;
+
+                    for x in [-half_width, 0.0, half_width] {
+                        spawn_splash(
+                            &mut commands,
+                            Vec2::new(transform.translation.x + x, surface),
+                            1.5,
+                        );
+                    }
+                }
+            });
+        }
+    }
+}
@@ -0,0 +1,215 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use rand::prelude::SliceRandom;
+use rand::{random, thread_rng, Rng};
+
+use crate::block::{Aiming, Block, Falling};
+use crate::collision_sounds::CollisionSound;
+use crate::environment::rain::DarkenSpriteOnRain;
+use crate::level::{Level, LevelLifecycle};
+use crate::state::LevelState;
+use crate::HORIZONTAL_VIEWPORT_SIZE;
+
+pub struct FlyingObstaclePlugin;
+
+impl Plugin for FlyingObstaclePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(LevelState::Playing), reset_flying_obstacle_spawner)
+            .add_systems(
+                Update,
+                (
+                    spawn_flying_obstacle_system,
+                    flying_obstacle_collision_system,
+                )
+                    .run_if(
+                        in_state(LevelState::Playing).or_else(in_state(LevelState::KeepPlaying)),
+                    ),
+            )
+            .init_resource::<FlyingObstacleSpawner>();
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum FlyingObstacleType {
+    Bird,
+    Drone,
+    Helicopter,
+}
+
+impl FlyingObstacleType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            FlyingObstacleType::Bird => "bird",
+            FlyingObstacleType::Drone => "drone",
+            FlyingObstacleType::Helicopter => "helicopter",
+        }
+    }
+
+    pub fn asset(&self) -> String {
+        format!("flyers/{}.png", self.name())
+    }
+
+    pub fn size(&self) -> Vec2 {
+        match self {
+            FlyingObstacleType::Bird => Vec2::new(1.0, 0.5),
+            FlyingObstacleType::Drone => Vec2::new(1.5, 0.6),
+            FlyingObstacleType::Helicopter => Vec2::new(4.8, 2.0),
+        }
+    }
+
+    pub fn speed(&self) -> std::ops::Range<f32> {
+        match self {
+            FlyingObstacleType::Bird => 4.0..7.0,
+            FlyingObstacleType::Drone => 2.0..4.0,
+            FlyingObstacleType::Helicopter => 3.0..5.0,
+        }
+    }
+
+    /// How hard a block is pushed away when it hits the obstacle
+    pub fn deflection(&self) -> f32 {
+        match self {
+            FlyingObstacleType::Bird => 3.0,
+            FlyingObstacleType::Drone => 6.0,
+            FlyingObstacleType::Helicopter => 15.0,
+        }
+    }
+}
+
+/// Per level configuration of the obstacles crossing the sky
+#[derive(Debug, Clone)]
+pub struct FlyingObstacles {
+    pub types: &'static [FlyingObstacleType],
+    /// Average seconds between two obstacles
+    pub interval: f32,
+    pub min_height: f32,
+    pub max_height: f32,
+    /// Whether obstacles also push blocks that are already part of the tower
+    pub nudge_settled: bool,
+}
+
+#[derive(Component, Debug)]
+pub struct FlyingObstacle {
+    pub obstacle_type: FlyingObstacleType,
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct FlyingObstacleSpawner {
+    pub timer: Timer,
+}
+
+fn next_spawn(obstacles: &FlyingObstacles) -> Timer {
+    Timer::from_seconds(
+        obstacles.interval * (0.5 + random::<f32>()),
+        TimerMode::Once,
+    )
+}
+
+pub fn reset_flying_obstacle_spawner(
+    mut spawner: ResMut<FlyingObstacleSpawner>,
+    level: Res<Level>,
+) {
+    if let Some(obstacles) = &level.flying_obstacles {
+        spawner.timer = next_spawn(obstacles);
+    }
+}
+
+pub fn spawn_flying_obstacle_system(
+    mut commands: Commands,
+    mut spawner: ResMut<FlyingObstacleSpawner>,
+    level: Res<Level>,
+    time: Res<Time>,
+    assets: Res<AssetServer>,
+) {
+    let Some(obstacles) = &level.flying_obstacles else {
+        return;
+    };
+
+    if !spawner.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+    spawner.timer = next_spawn(obstacles);
+
+    let Some(obstacle_type) = obstacles.types.choose(&mut thread_rng()) else {
+        return;
+    };
+
+    let size = obstacle_type.size();
+    let forward = random::<bool>();
+    let direction = if forward { 1.0 } else { -1.0 };
+
+    let position = Vec2::new(
+        -direction * HORIZONTAL_VIEWPORT_SIZE * 0.9,
+        thread_rng().gen_range(obstacles.min_height..obstacles.max_height),
+    );
+    let velocity = Vec2::X * direction * thread_rng().gen_range(obstacle_type.speed());
+
+    commands.spawn((
+        FlyingObstacle {
+            obstacle_type: *obstacle_type,
+        },
+        LevelLifecycle,
+        CollisionSound {
+            weight: 0.5,
+            ..Default::default()
+        },
+        SpriteBundle {
+            transform: Transform::from_xyz(position.x, position.y, 1.0),
+            texture: assets.load(obstacle_type.asset()),
+            sprite: Sprite {
+                custom_size: Some(size),
+                flip_x: !forward,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        DarkenSpriteOnRain(1.0),
+        RigidBody::KinematicVelocityBased,
+        Velocity::linear(velocity),
+        Collider::cuboid(size.x / 2.0, size.y / 2.0),
+        // Blocks are deflected by hand, so obstacles don't shove the tower with infinite mass
+        Sensor,
+        ActiveEvents::COLLISION_EVENTS,
+    ));
+}
+
+pub fn flying_obstacle_collision_system(
+    mut collision_events: EventReader<CollisionEvent>,
+    obstacle_query: Query<(&FlyingObstacle, &Transform, &Velocity)>,
+    mut block_query: Query<
+        (&Transform, &mut ExternalImpulse, Has<Falling>),
+        (With<Block>, Without<Aiming>),
+    >,
+    level: Res<Level>,
+) {
+    let nudge_settled = level
+        .flying_obstacles
+        .as_ref()
+        .map(|obstacles| obstacles.nudge_settled)
+        .unwrap_or(false);
+
+    for event in collision_events.read() {
+        if let CollisionEvent::Started(a, b, _) = event {
+            [(a, b), (b, a)].into_iter().for_each(|(obstacle, block)| {
+                let Ok((obstacle, obstacle_transform, obstacle_velocity)) =
+                    obstacle_query.get(*obstacle)
+                else {
+                    return;
+                };
+
+                if let Ok((transform, mut impulse, falling)) = block_query.get_mut(*block) {
+                    let away = (transform.translation.xy() - obstacle_transform.translation.xy())
+                        .normalize_or_zero();
+                    let push = (away + obstacle_velocity.linvel.normalize_or_zero())
+                        .normalize_or_zero()
+                        * obstacle.obstacle_type.deflection();
+
+                    if falling {
+                        impulse.impulse += push;
+                    } else if nudge_settled {
+                        impulse.impulse += push * 0.3;
+                    }
+                }
+            });
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use bevy::audio::{Volume, VolumeLevel};
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+use rand::random;
+
+use crate::block::{Aiming, Block, Falling};
+use crate::debris::destroy_block;
+use crate::effect::lightning_rod::LightningRodEffect;
+use crate::level::{block_top, is_settled, Level, LevelLifecycle, UpdateLevelStats};
+use crate::state::LevelState;
+use crate::{FLOOR_HEIGHT, HORIZONTAL_VIEWPORT_SIZE};
+
+pub struct LightningPlugin;
+
+impl Plugin for LightningPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(LevelState::Playing), reset_lightning)
+            .add_systems(
+                Update,
+                (
+                    lightning_system.run_if(
+                        in_state(LevelState::Playing).or_else(in_state(LevelState::KeepPlaying)),
+                    ),
+                    lightning_flash_system,
+                ),
+            )
+            .init_resource::<LightningTimer>();
+    }
+}
+
+/// Number of segments of a bolt, each one is offset a little to make the bolt look jagged
+pub const LIGHTNING_SEGMENTS: usize = 8;
+
+#[derive(Debug, Clone)]
+pub struct Lightning {
+    /// Average seconds between two strikes
+    pub interval: f32,
+    /// Impulse that knocks the struck block over
+    pub force: f32,
+    /// Chance that the struck block is shattered into debris instead of knocked over
+    pub debris_chance: f32,
+}
+
+impl Lightning {
+    pub fn next_strike(&self) -> Timer {
+        Timer::from_seconds(self.interval * (0.5 + random::<f32>()), TimerMode::Once)
+    }
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct LightningTimer(pub Timer);
+
+/// Bolt segments and the screen flash, both fade out quickly
+#[derive(Component, Debug)]
+pub struct LightningFlash {
+    pub timer: Timer,
+    pub alpha: f32,
+}
+
+pub fn reset_lightning(mut timer: ResMut<LightningTimer>, level: Res<Level>) {
+    if let Some(lightning) = &level.lightning {
+        timer.0 = lightning.next_strike();
+    }
+}
+
+pub fn lightning_system(
+    mut commands: Commands,
+    mut timer: ResMut<LightningTimer>,
+    mut blocks: Query<
+        (
+            Entity,
+            &Block,
+            &Transform,
+            &Velocity,
+            &mut ExternalImpulse,
+            Has<LightningRodEffect>,
+        ),
+        (Without<Falling>, Without<Aiming>),
+    >,
+    mut update_level_stats_events: EventWriter<UpdateLevelStats>,
+    level: Res<Level>,
+    time: Res<Time>,
+    assets: Res<AssetServer>,
+) {
+    let Some(lightning) = &level.lightning else {
+        return;
+    };
+
+    if !timer.0.tick(time.delta()).just_finished() {
+        return;
+    }
+    timer.0 = lightning.next_strike();
+
+    let highest = |rods_only: bool| {
+        blocks
+            .iter()
+            .filter(|(_, _, _, velocity, _, rod)| is_settled(velocity) && (*rod || !rods_only))
+            .map(|(entity, block, transform, _, _, rod)| (entity, block_top(block, transform), rod))
+            .max_by(|(_, a, _), (_, b, _)| a.partial_cmp(b).unwrap())
+    };
+
+    // Lightning rods attract the strike, even if they are not the tallest block
+    let target = highest(true).or_else(|| highest(false));
+
+    let strike_position = if let Some((entity, top, protected)) = target {
+        let (entity, block, transform, velocity, mut impulse, _) = blocks.get_mut(entity).unwrap();
+        let position = Vec2::new(transform.translation.x, top);
+
+        if !protected {
+            if random::<f32>() < lightning.debris_chance {
+                destroy_block(&mut commands, &assets, entity, block, transform, velocity);
+                update_level_stats_events.send(UpdateLevelStats::BlockDestroyed);
+            } else {
+                let direction = if random() { 1.0 } else { -1.0 };
+                impulse.impulse += Vec2::new(direction, -0.5) * lightning.force;
+            }
+        }
+
+        position
+    } else {
+        // Nothing to hit, strike somewhere in the city
+        Vec2::new(
+            (random::<f32>() - 0.5) * HORIZONTAL_VIEWPORT_SIZE,
+            FLOOR_HEIGHT,
+        )
+    };
+
+    spawn_bolt(&mut commands, strike_position);
+
+    commands.spawn((
+        LightningFlash {
+            timer: Timer::from_seconds(0.4, TimerMode::Once),
+            alpha: 0.6,
+        },
+        LevelLifecycle,
+        SpriteBundle {
+            transform: Transform::from_xyz(0.0, strike_position.y, 50.0),
+            sprite: Sprite {
+                color: Color::rgba(1.0, 1.0, 1.0, 0.6),
+                custom_size: Some(Vec2::new(HORIZONTAL_VIEWPORT_SIZE * 2.0, 200.0)),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    ));
+
+    commands.spawn(AudioBundle {
+        source: assets.load("sounds/thunder.wav"),
+        settings: PlaybackSettings {
+            volume: Volume::Relative(VolumeLevel::new(0.9)),
+            ..PlaybackSettings::DESPAWN
+        },
+    });
+}
+
+fn spawn_bolt(commands: &mut Commands, target: Vec2) {
+    let start = target + Vec2::new((random::<f32>() - 0.5) * 6.0, 40.0);
+
+    let mut from = start;
+    for i in 1..=LIGHTNING_SEGMENTS {
+        let progress = i as f32 / LIGHTNING_SEGMENTS as f32;
+        let to = if i == LIGHTNING_SEGMENTS {
+            target
+        } else {
+            start.lerp(target, progress) + Vec2::X * (random::<f32>() - 0.5) * 2.0
+        };
+
+        let segment = to - from;
+
+        commands.spawn((
+            LightningFlash {
+                timer: Timer::from_seconds(0.25, TimerMode::Once),
+                alpha: 1.0,
+            },
+            LevelLifecycle,
+            SpriteBundle {
+                transform: Transform::from_translation(Vec3::from(((from + to) / 2.0, 3.0)))
+                    .with_rotation(Quat::from_rotation_z(Vec2::Y.angle_between(segment))),
+                sprite: Sprite {
+                    color: Color::rgb(0.9, 0.9, 1.0),
+                    custom_size: Some(Vec2::new(0.15, segment.length())),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ));
+
+        from = to;
+    }
+}
+
+pub fn lightning_flash_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut LightningFlash, &mut Sprite)>,
+) {
+    for (entity, mut flash, mut sprite) in query.iter_mut() {
+        flash.timer.tick(time.delta());
+        sprite.color.set_a(flash.alpha * flash.timer.percent_left());
+        if flash.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
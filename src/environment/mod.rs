@@ -4,10 +4,17 @@ mod beam;
 mod car;
 pub mod city;
 mod debris_cleaner;
+pub mod earthquake;
 pub mod fees;
+pub mod flood;
+pub mod flying_obstacle;
+pub mod lightning;
 mod music;
 pub mod rain;
+pub mod snow;
 mod tow_truck;
+pub mod wind;
+pub mod wrecking_ball;
 
 pub struct EnvironmentPlugin;
 
@@ -22,6 +29,13 @@ impl Plugin for EnvironmentPlugin {
             city::CityPlugin,
             rain::RainPlugin,
             music::MusicPlugin,
+            wind::WindPlugin,
+            earthquake::EarthquakePlugin,
+            flood::FloodPlugin,
+            snow::SnowPlugin,
+            lightning::LightningPlugin,
+            flying_obstacle::FlyingObstaclePlugin,
+            wrecking_ball::WreckingBallPlugin,
         ));
     }
 }
@@ -65,7 +65,7 @@ pub fn spawn_rain(
 ) {
     if let Some(rain_amount) = level.rain {
         if spawner.timer.tick(time.delta()).just_finished() {
-            for i in 0..rain_amount {
+            for _i in 0..rain_amount {
                 let x = rand::random::<f32>() * HORIZONTAL_VIEWPORT_SIZE
                     - HORIZONTAL_VIEWPORT_SIZE / 2.0;
                 let y = 30.0 + random::<f32>() * 30.0;
@@ -111,55 +111,57 @@ pub fn spawn_rain(
 pub fn rain_collision_system(
     mut commands: Commands,
     mut collisions: EventReader<CollisionEvent>,
-    rain_query: Query<(&Transform), With<Rain>>,
-    splash_query: Query<(&Transform), With<Splash>>,
+    rain_query: Query<&Transform, With<Rain>>,
+    splash_query: Query<&Transform, With<Splash>>,
 ) {
     for event in collisions.read() {
-        match event {
-            CollisionEvent::Started(collider1, collider2, _) => {
-                [(collider1, collider2), (collider2, collider1)]
-                    .into_iter()
-                    .for_each(|(rain_entity, other_entity)| {
-                        if let Ok(transform) = rain_query.get(*rain_entity) {
-                            commands.entity(*rain_entity).despawn_recursive();
-
-                            let mut transform = transform.clone();
-                            transform.translation.y += 0.1 * random::<f32>() + 0.1;
-
-                            for i in -1..=1 {
-                                commands.spawn((
-                                    Collider::ball(0.025),
-                                    Splash,
-                                    SpriteBundle {
-                                        sprite: Sprite {
-                                            color: Color::rgba_u8(200, 200, 255, 200),
-                                            custom_size: Some(Vec2::new(0.05, 0.05)),
-                                            ..Default::default()
-                                        },
-                                        transform,
-                                        ..Default::default()
-                                    },
-                                    RigidBody::Dynamic,
-                                    Velocity::linear(Vec2::new(i as f32 * 3.0, 3.0)),
-                                    ActiveEvents::COLLISION_EVENTS,
-                                    Sensor,
-                                    CollisionGroups {
-                                        memberships: RAIN_COLLISION_GROUP,
-                                        filters: Group::ALL & !RAIN_COLLISION_GROUP,
-                                    },
-                                ));
-                            }
-                        }
-                        if let Ok(transform) = splash_query.get(*other_entity) {
-                            commands.entity(*other_entity).despawn_recursive();
-                        }
-                    });
-            }
-            _ => {}
+        if let CollisionEvent::Started(collider1, collider2, _) = event {
+            [(collider1, collider2), (collider2, collider1)]
+                .into_iter()
+                .for_each(|(rain_entity, other_entity)| {
+                    if let Ok(transform) = rain_query.get(*rain_entity) {
+                        commands.entity(*rain_entity).despawn_recursive();
+
+                        spawn_splash(&mut commands, transform.translation.xy(), 1.0);
+                    }
+                    if let Ok(_transform) = splash_query.get(*other_entity) {
+                        commands.entity(*other_entity).despawn_recursive();
+                    }
+                });
         }
     }
 }
 
+/// Spawns a few droplets that jump up from `position`, `strength` scales the speed of the droplets
+pub fn spawn_splash(commands: &mut Commands, position: Vec2, strength: f32) {
+    let mut transform = Transform::from_xyz(position.x, position.y, 0.0);
+    transform.translation.y += 0.1 * random::<f32>() + 0.1;
+
+    for i in -1..=1 {
+        commands.spawn((
+            Collider::ball(0.025),
+            Splash,
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::rgba_u8(200, 200, 255, 200),
+                    custom_size: Some(Vec2::new(0.05, 0.05)),
+                    ..Default::default()
+                },
+                transform,
+                ..Default::default()
+            },
+            RigidBody::Dynamic,
+            Velocity::linear(Vec2::new(i as f32 * 3.0, 3.0) * strength),
+            ActiveEvents::COLLISION_EVENTS,
+            Sensor,
+            CollisionGroups {
+                memberships: RAIN_COLLISION_GROUP,
+                filters: Group::ALL & !RAIN_COLLISION_GROUP,
+            },
+        ));
+    }
+}
+
 pub fn darken_sprite_on_rain_system(
     mut query: Query<(&mut Sprite, &DarkenSpriteOnRain)>,
     level: Res<Level>,
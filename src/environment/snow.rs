@@ -0,0 +1,272 @@
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+use bevy::utils::HashMap;
+use bevy_rapier2d::prelude::*;
+use rand::random;
+
+use crate::base::Base;
+use crate::block::{Aiming, Block, Falling};
+use crate::consts::{foreground_collision_groups, RAIN_COLLISION_GROUP};
+use crate::effect::ice::Frozen;
+use crate::environment::wind::Wind;
+use crate::level::Level;
+use crate::HORIZONTAL_VIEWPORT_SIZE;
+
+pub struct SnowPlugin;
+
+impl Plugin for SnowPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (
+                spawn_snow,
+                snowflake_collision_system,
+                shake_off_snow_system,
+                snow_load_system,
+            ),
+        )
+        .init_resource::<SnowSpawner>();
+    }
+}
+
+/// Mass every flake adds to the block it lands on
+pub const SNOW_MASS_PER_FLAKE: f32 = 0.01;
+pub const MAX_SNOW_MASS: f32 = 2.0;
+/// Fraction of the friction that is lost under a full snow load
+pub const SNOW_FRICTION_LOSS: f32 = 0.6;
+pub const MAX_SNOW_CAP_HEIGHT: f32 = 0.35;
+/// Impacts faster than this knock all snow off a block
+pub const SNOW_SHAKE_OFF_VELOCITY: f32 = 3.0;
+
+#[derive(Component, Debug)]
+pub struct Snowflake;
+
+#[derive(Component, Debug)]
+pub struct SnowCap;
+
+/// Snow that piled up on a settled block
+#[derive(Component, Debug)]
+pub struct SnowLoad {
+    pub mass: f32,
+    /// Friction of the block before it was snowed in
+    pub base_friction: Friction,
+    pub cap: Entity,
+}
+
+impl SnowLoad {
+    pub fn fraction(&self) -> f32 {
+        self.mass / MAX_SNOW_MASS
+    }
+}
+
+#[derive(Resource, Debug)]
+pub struct SnowSpawner {
+    pub timer: Timer,
+}
+
+impl Default for SnowSpawner {
+    fn default() -> Self {
+        Self {
+            timer: Timer::from_seconds(0.05, TimerMode::Repeating),
+        }
+    }
+}
+
+pub fn spawn_snow(
+    mut commands: Commands,
+    mut spawner: ResMut<SnowSpawner>,
+    time: Res<Time>,
+    level: Res<Level>,
+    wind: Res<Wind>,
+) {
+    if let Some(snow_amount) = level.snow {
+        if spawner.timer.tick(time.delta()).just_finished() {
+            for _ in 0..snow_amount {
+                let x = random::<f32>() * HORIZONTAL_VIEWPORT_SIZE - HORIZONTAL_VIEWPORT_SIZE / 2.0;
+                let y = 30.0 + random::<f32>() * 30.0;
+
+                let collision_group = if random() {
+                    CollisionGroups {
+                        memberships: RAIN_COLLISION_GROUP,
+                        filters: Group::ALL & !RAIN_COLLISION_GROUP,
+                    }
+                } else {
+                    CollisionGroups {
+                        memberships: RAIN_COLLISION_GROUP,
+                        filters: foreground_collision_groups() & !RAIN_COLLISION_GROUP,
+                    }
+                };
+
+                // Flakes are light, so the wind carries them along
+                let drift = (random::<f32>() - 0.5) + wind.acceleration * 0.3;
+
+                commands.spawn((
+                    Snowflake,
+                    Collider::ball(0.06),
+                    SpriteBundle {
+                        sprite: Sprite {
+                            color: Color::rgba(1.0, 1.0, 1.0, 0.9),
+                            custom_size: Some(Vec2::splat(0.12)),
+                            ..Default::default()
+                        },
+                        transform: Transform::from_xyz(x, y, 0.0),
+                        ..Default::default()
+                    },
+                    RigidBody::KinematicVelocityBased,
+                    Velocity::linear(Vec2::new(drift, -2.0 - random::<f32>())),
+                    ActiveEvents::COLLISION_EVENTS,
+                    ActiveCollisionTypes::DYNAMIC_KINEMATIC
+                        | ActiveCollisionTypes::KINEMATIC_STATIC
+                        | ActiveCollisionTypes::KINEMATIC_KINEMATIC,
+                    Sensor,
+                    collision_group,
+                ));
+            }
+        }
+    }
+}
+
+pub fn snowflake_collision_system(
+    mut commands: Commands,
+    mut collisions: EventReader<CollisionEvent>,
+    flake_query: Query<(), With<Snowflake>>,
+    mut block_query: Query<
+        (&Block, &Friction, Option<&Frozen>, Option<&mut SnowLoad>),
+        (Without<Falling>, Without<Aiming>),
+    >,
+) {
+    // Several flakes can hit a block that has no snow yet in the same frame
+    let mut hits = HashMap::new();
+
+    for event in collisions.read() {
+        if let CollisionEvent::Started(a, b, _) = event {
+            [(a, b), (b, a)].into_iter().for_each(|(flake, other)| {
+                if flake_query.get(*flake).is_err() {
+                    return;
+                }
+
+                commands.entity(*flake).despawn_recursive();
+
+                if block_query.contains(*other) {
+                    *hits.entry(*other).or_insert(0) += 1;
+                }
+            });
+        }
+    }
+
+    for (entity, flakes) in hits {
+        let Ok((block, friction, frozen, snow_load)) = block_query.get_mut(entity) else {
+            continue;
+        };
+
+        let mass = flakes as f32 * SNOW_MASS_PER_FLAKE;
+
+        if let Some(mut snow_load) = snow_load {
+            snow_load.mass = (snow_load.mass + mass).min(MAX_SNOW_MASS);
+        } else {
+            let cap = commands
+                .spawn((
+                    SnowCap,
+                    SpriteBundle {
+                        transform: Transform::from_xyz(0.0, block.block_type.height() / 2.0, 1.5),
+                        sprite: Sprite {
+                            color: Color::rgba(1.0, 1.0, 1.0, 0.95),
+                            anchor: Anchor::BottomCenter,
+                            // Grows with the load in `snow_load_system`
+                            custom_size: Some(Vec2::new(block.block_type.width(), 0.0)),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    },
+                ))
+                .id();
+
+            commands.entity(entity).add_child(cap).insert((
+                SnowLoad {
+                    mass,
+                    base_friction: frozen.map_or(*friction, |frozen| frozen.previous_friction),
+                    cap,
+                },
+                AdditionalMassProperties::Mass(mass),
+            ));
+        }
+    }
+}
+
+pub fn snow_load_system(
+    mut query: Query<
+        (
+            &Block,
+            &SnowLoad,
+            &mut Friction,
+            &mut AdditionalMassProperties,
+            Option<&mut Frozen>,
+        ),
+        Changed<SnowLoad>,
+    >,
+    mut cap_query: Query<&mut Sprite, With<SnowCap>>,
+) {
+    for (block, snow_load, mut friction, mut mass, frozen) in query.iter_mut() {
+        *mass = AdditionalMassProperties::Mass(snow_load.mass);
+
+        let mut snowed_friction = snow_load.base_friction;
+        snowed_friction.coefficient *= 1.0 - SNOW_FRICTION_LOSS * snow_load.fraction();
+
+        // Frozen blocks keep their grip, the snow only matters once they thaw
+        match frozen {
+            Some(mut frozen) => frozen.previous_friction = snowed_friction,
+            None => *friction = snowed_friction,
+        }
+
+        if let Ok(mut sprite) = cap_query.get_mut(snow_load.cap) {
+            sprite.custom_size = Some(Vec2::new(
+                block.block_type.width(),
+                MAX_SNOW_CAP_HEIGHT * snow_load.fraction(),
+            ));
+        }
+    }
+}
+
+pub fn shake_off_snow_system(
+    mut commands: Commands,
+    mut collisions: EventReader<CollisionEvent>,
+    mut snow_query: Query<(
+        &SnowLoad,
+        &mut Friction,
+        &mut AdditionalMassProperties,
+        Option<&mut Frozen>,
+    )>,
+    velocity_query: Query<&Velocity>,
+    solid_query: Query<(), Or<(With<Block>, With<Base>)>>,
+) {
+    for event in collisions.read() {
+        if let CollisionEvent::Started(a, b, _) = event {
+            // Snowflakes and rain splashes easily pass the threshold, only block and base impacts count
+            if !solid_query.contains(*a) || !solid_query.contains(*b) {
+                continue;
+            }
+
+            let (Ok(velocity_a), Ok(velocity_b)) = (velocity_query.get(*a), velocity_query.get(*b))
+            else {
+                continue;
+            };
+
+            if (velocity_a.linvel - velocity_b.linvel).length() < SNOW_SHAKE_OFF_VELOCITY {
+                continue;
+            }
+
+            for entity in [a, b] {
+                if let Ok((snow_load, mut friction, mut mass, frozen)) = snow_query.get_mut(*entity)
+                {
+                    match frozen {
+                        Some(mut frozen) => frozen.previous_friction = snow_load.base_friction,
+                        None => *friction = snow_load.base_friction,
+                    }
+                    *mass = AdditionalMassProperties::Mass(0.0);
+                    commands.entity(snow_load.cap).despawn_recursive();
+                    commands.entity(*entity).remove::<SnowLoad>();
+                }
+            }
+        }
+    }
+}
@@ -77,7 +77,7 @@ pub fn tow_car_system(
     for (tow_truck_entity, mut tow_truck, tow_truck_transform, mut tow_truck_velocity) in
         tow_truck_query.iter_mut()
     {
-        if let Ok((car_entity, mut car, mut car_transform, mut car_velocity)) =
+        if let Ok((car_entity, _car, mut car_transform, mut car_velocity)) =
             car_query.get_mut(tow_truck.target)
         {
             match tow_truck.phase {
@@ -99,8 +99,7 @@ pub fn tow_car_system(
                         ));
                         tow_truck_velocity.linvel = Vec2::ZERO;
 
-                        car_transform.translation =
-                            car_transform.translation - tow_truck_transform.translation;
+                        car_transform.translation -= tow_truck_transform.translation;
                     }
                 }
                 TowTruckPhase::RaisingCar => {
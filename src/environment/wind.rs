@@ -0,0 +1,106 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::block::{Aiming, Block, Falling};
+use crate::level::Level;
+use crate::state::LevelState;
+use crate::throw::TargetIndicator;
+use crate::PHYSICS_DT;
+
+pub struct WindPlugin;
+
+impl Plugin for WindPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(LevelState::Playing), reset_wind)
+            .add_systems(
+                Update,
+                update_wind_system.run_if(
+                    in_state(LevelState::Playing).or_else(in_state(LevelState::KeepPlaying)),
+                ),
+            )
+            .add_systems(
+                FixedUpdate,
+                wind_force_system.run_if(
+                    in_state(LevelState::Playing).or_else(in_state(LevelState::KeepPlaying)),
+                ),
+            )
+            .init_resource::<Wind>();
+    }
+}
+
+/// Settled blocks are sheltered by the tower, they only feel a fraction of the wind
+pub const SETTLED_WIND_FACTOR: f32 = 0.1;
+
+/// Per level wind configuration, strengths are horizontal accelerations in m/s², positive blows
+/// to the right
+#[derive(Debug, Clone)]
+pub enum WindPattern {
+    Constant(f32),
+    /// Blows in one direction, swelling between 20% and full strength
+    Gusting {
+        strength: f32,
+        period: f32,
+    },
+    /// Slowly turns around, blowing the other way after half a period
+    Changing {
+        strength: f32,
+        period: f32,
+    },
+}
+
+impl WindPattern {
+    pub fn acceleration(&self, elapsed: f32) -> f32 {
+        match *self {
+            WindPattern::Constant(strength) => strength,
+            WindPattern::Gusting { strength, period } => {
+                strength * (0.6 + 0.4 * (elapsed * TAU / period).sin())
+            }
+            WindPattern::Changing { strength, period } => strength * (elapsed * TAU / period).cos(),
+        }
+    }
+}
+
+/// Current wind, shared by the physics and the trajectory preview
+#[derive(Resource, Debug, Default)]
+pub struct Wind {
+    pub acceleration: f32,
+    pub elapsed: f32,
+}
+
+impl Wind {
+    pub fn acceleration(&self) -> Vec2 {
+        Vec2::X * self.acceleration
+    }
+}
+
+pub fn reset_wind(mut wind: ResMut<Wind>) {
+    *wind = Wind::default();
+}
+
+pub fn update_wind_system(mut wind: ResMut<Wind>, level: Res<Level>, time: Res<Time>) {
+    wind.elapsed += time.delta_seconds();
+    wind.acceleration = level
+        .wind
+        .as_ref()
+        .map(|pattern| pattern.acceleration(wind.elapsed))
+        .unwrap_or(0.0);
+}
+
+pub fn wind_force_system(
+    wind: Res<Wind>,
+    mut query: Query<
+        (&mut ExternalImpulse, &ReadMassProperties, Has<Falling>),
+        (With<Block>, Without<Aiming>, Without<TargetIndicator>),
+    >,
+) {
+    if wind.acceleration == 0.0 {
+        return;
+    }
+
+    for (mut impulse, mass, falling) in query.iter_mut() {
+        let factor = if falling { 1.0 } else { SETTLED_WIND_FACTOR };
+        impulse.impulse += wind.acceleration() * factor * mass.mass * PHYSICS_DT;
+    }
+}
@@ -0,0 +1,228 @@
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::*;
+
+use crate::collision_sounds::CollisionSound;
+use crate::level::{Level, LevelLifecycle};
+use crate::state::LevelState;
+
+pub struct WreckingBallPlugin;
+
+impl Plugin for WreckingBallPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(LevelState::Playing), setup_wrecking_ball)
+            .add_systems(
+                Update,
+                (wrecking_ball_swing_system, wrecking_ball_chain_system).run_if(
+                    in_state(LevelState::Playing).or_else(in_state(LevelState::KeepPlaying)),
+                ),
+            );
+    }
+}
+
+/// Motor settings used to hoist the ball back to its release angle
+const HOIST_STIFFNESS: f32 = 30.0;
+const HOIST_DAMPING: f32 = 11.0;
+
+/// Spacing of the warning dots along the arc in radians
+const ARC_DOT_SPACING: f32 = 0.04;
+
+/// A heavy ball hanging from a revolute joint. It is held at `release_angle`, let go to sweep
+/// across the site and hoisted back again after every swing.
+#[derive(Debug, Clone)]
+pub struct WreckingBall {
+    pub pivot: Vec2,
+    pub length: f32,
+    pub radius: f32,
+    /// Radians from hanging straight down, positive values hold the ball to the right of the pivot
+    pub release_angle: f32,
+    /// Seconds between two swings, including hoisting the ball back up
+    pub interval: f32,
+}
+
+impl WreckingBall {
+    pub fn position(&self, angle: f32) -> Vec2 {
+        self.pivot + Vec2::from_angle(angle).rotate(Vec2::NEG_Y * self.length)
+    }
+
+    /// Motor target that holds the ball at `release_angle`. The motor acts like a spring, so
+    /// it has to aim a bit higher to make up for gravity pulling the ball down.
+    pub fn hold_angle(&self, gravity: f32) -> f32 {
+        let sag = gravity.abs() * self.release_angle.sin() / (self.length * HOIST_STIFFNESS);
+        self.release_angle + sag
+    }
+
+    /// Time to swing across and back, the small angle approximation is good enough here
+    pub fn period(&self, gravity: f32) -> f32 {
+        2.0 * PI * (self.length / gravity.abs()).sqrt()
+    }
+}
+
+#[derive(Component, Debug)]
+pub enum WreckingBallPhase {
+    Holding(Timer),
+    Swinging(Timer),
+}
+
+#[derive(Component, Debug)]
+pub struct WreckingBallChain {
+    pub ball: Entity,
+}
+
+pub fn setup_wrecking_ball(mut commands: Commands, level: Res<Level>, assets: Res<AssetServer>) {
+    let Some(wrecking_ball) = &level.wrecking_ball else {
+        return;
+    };
+
+    let anchor = commands
+        .spawn((
+            LevelLifecycle,
+            TransformBundle::from_transform(Transform::from_xyz(
+                wrecking_ball.pivot.x,
+                wrecking_ball.pivot.y,
+                0.0,
+            )),
+            RigidBody::Fixed,
+        ))
+        .id();
+
+    let joint = RevoluteJointBuilder::new()
+        .local_anchor1(Vec2::ZERO)
+        .local_anchor2(Vec2::Y * wrecking_ball.length)
+        .motor_position(
+            wrecking_ball.hold_angle(level.physics.gravity),
+            HOIST_STIFFNESS,
+            HOIST_DAMPING,
+        );
+
+    let position = wrecking_ball.position(wrecking_ball.release_angle);
+    let ball = commands
+        .spawn((
+            WreckingBallPhase::Holding(Timer::from_seconds(
+                wrecking_ball.interval,
+                TimerMode::Once,
+            )),
+            LevelLifecycle,
+            CollisionSound {
+                weight: 3.0,
+                ..Default::default()
+            },
+            SpriteBundle {
+                transform: Transform::from_xyz(position.x, position.y, 1.0)
+                    .with_rotation(Quat::from_rotation_z(wrecking_ball.release_angle)),
+                texture: assets.load("circle.png"),
+                sprite: Sprite {
+                    color: Color::rgb(0.15, 0.15, 0.17),
+                    custom_size: Some(Vec2::splat(wrecking_ball.radius * 2.0)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            RigidBody::Dynamic,
+            Collider::ball(wrecking_ball.radius),
+            ColliderMassProperties::Density(20.0),
+            ImpulseJoint::new(anchor, joint),
+            Sleeping::disabled(),
+            Ccd::enabled(),
+        ))
+        .id();
+
+    commands.spawn((
+        WreckingBallChain { ball },
+        LevelLifecycle,
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::rgb(0.3, 0.3, 0.3),
+                custom_size: Some(Vec2::new(0.15, 1.0)),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    ));
+
+    // Faint warning path the ball sweeps along
+    let max_angle = wrecking_ball.release_angle.abs();
+    let dots = (max_angle * 2.0 / ARC_DOT_SPACING) as usize;
+    for i in 0..=dots {
+        let angle = -max_angle + i as f32 * ARC_DOT_SPACING;
+        let position = wrecking_ball.position(angle);
+        commands.spawn((
+            LevelLifecycle,
+            SpriteBundle {
+                transform: Transform::from_xyz(position.x, position.y, 0.5),
+                texture: assets.load("circle.png"),
+                sprite: Sprite {
+                    color: Color::rgba(1.0, 0.3, 0.2, 0.25),
+                    custom_size: Some(Vec2::splat(0.2)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ));
+    }
+}
+
+pub fn wrecking_ball_swing_system(
+    mut query: Query<(&mut WreckingBallPhase, &mut ImpulseJoint)>,
+    level: Res<Level>,
+    time: Res<Time>,
+) {
+    let Some(wrecking_ball) = &level.wrecking_ball else {
+        return;
+    };
+
+    for (mut phase, mut joint) in query.iter_mut() {
+        let Some(revolute) = joint.data.as_revolute_mut() else {
+            continue;
+        };
+
+        match phase.as_mut() {
+            WreckingBallPhase::Holding(timer) => {
+                if timer.tick(time.delta()).just_finished() {
+                    revolute.set_motor_position(wrecking_ball.release_angle, 0.0, 0.0);
+                    *phase = WreckingBallPhase::Swinging(Timer::from_seconds(
+                        wrecking_ball.period(level.physics.gravity),
+                        TimerMode::Once,
+                    ));
+                }
+            }
+            WreckingBallPhase::Swinging(timer) => {
+                if timer.tick(time.delta()).just_finished() {
+                    revolute.set_motor_position(
+                        wrecking_ball.hold_angle(level.physics.gravity),
+                        HOIST_STIFFNESS,
+                        HOIST_DAMPING,
+                    );
+                    *phase = WreckingBallPhase::Holding(Timer::from_seconds(
+                        wrecking_ball.interval,
+                        TimerMode::Once,
+                    ));
+                }
+            }
+        }
+    }
+}
+
+pub fn wrecking_ball_chain_system(
+    mut chain_query: Query<(&WreckingBallChain, &mut Transform)>,
+    ball_query: Query<&Transform, Without<WreckingBallChain>>,
+    level: Res<Level>,
+) {
+    let Some(wrecking_ball) = &level.wrecking_ball else {
+        return;
+    };
+
+    for (chain, mut transform) in chain_query.iter_mut() {
+        let Ok(ball) = ball_query.get(chain.ball) else {
+            continue;
+        };
+
+        let offset = ball.translation.xy() - wrecking_ball.pivot;
+        let center = wrecking_ball.pivot + offset / 2.0;
+
+        *transform = Transform::from_xyz(center.x, center.y, 0.9)
+            .with_rotation(Quat::from_rotation_z(Vec2::Y.angle_between(offset)))
+            .with_scale(Vec3::new(1.0, offset.length(), 1.0));
+    }
+}
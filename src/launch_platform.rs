@@ -1,11 +1,12 @@
 use bevy::prelude::*;
 use bevy::sprite::Anchor;
 use bevy_rapier2d::prelude::*;
-use bevy_tweening::lens::{TransformRotateXLens, TransformRotationLens, TransformScaleLens};
-use bevy_tweening::{Animator, EaseFunction, RepeatCount, RepeatStrategy, Tween};
+use bevy_tweening::lens::TransformRotationLens;
+use bevy_tweening::{Animator, EaseFunction, Tween};
 use std::time::Duration;
 
-use crate::level::{LaunchPlatformKind, Level, LevelLifecycle};
+use crate::crane::spawn_crane;
+use crate::level::{LaunchPlatformKind, Level, LevelLifecycle, LevelStats};
 use crate::state::LevelState;
 use crate::throw::Aim;
 use crate::{ASSET_SCALE, HORIZONTAL_VIEWPORT_SIZE};
@@ -14,21 +15,103 @@ pub struct LaunchPlatformPlugin;
 
 impl Plugin for LaunchPlatformPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(LevelState::Playing), (spawn_launch_platform_system))
+        app.add_systems(OnEnter(LevelState::Playing), spawn_launch_platform_system)
             .add_systems(
                 Update,
                 (
-                    (barrel_rotation_system, launch_platform_control_system).run_if(
-                        in_state(LevelState::Playing).or_else(in_state(LevelState::KeepPlaying)),
-                    ),
+                    (
+                        switch_launch_platform_system,
+                        refuel_system,
+                        highlight_active_launch_platform_system,
+                        barrel_rotation_system,
+                        launch_platform_control_system,
+                    )
+                        .run_if(
+                            in_state(LevelState::Playing)
+                                .or_else(in_state(LevelState::KeepPlaying)),
+                        ),
                     keep_launch_platform_visible_system,
                 ),
             );
     }
 }
 
+#[derive(Component, Debug)]
+pub struct LaunchPlatform {
+    /// Position in `Level::launch_platforms`, used to cycle through the platforms
+    pub index: usize,
+    pub kind: LaunchPlatformKind,
+}
+
+/// Mass of a free platform, used to calculate the recoil of a throw
+pub const LAUNCH_PLATFORM_MASS: f32 = 10.0;
+/// Meters per second the recoil of a free platform loses every second
+const RECOIL_DRAG: f32 = 1.0;
+
+/// Part of the velocity of a free platform that comes from throwing. It isn't braked like
+/// the thrust from WASD and slowly fades, so the player has to steer against it.
 #[derive(Component, Debug, Default)]
-pub struct LaunchPlatform;
+pub struct Recoil(pub Vec2);
+
+impl Recoil {
+    /// Velocity a free platform gains from throwing a block, momentum is conserved
+    pub fn push(block_velocity: Vec2, block_mass: f32) -> Vec2 {
+        -block_velocity * block_mass / LAUNCH_PLATFORM_MASS
+    }
+
+    pub fn fade(&mut self, dt: f32) {
+        let drag = RECOIL_DRAG * dt;
+        self.0 = if self.0.length() > drag {
+            self.0 - self.0.normalize() * drag
+        } else {
+            Vec2::ZERO
+        };
+    }
+}
+
+/// The platform the player currently controls and throws from
+#[derive(Component, Debug)]
+pub struct ActiveLaunchPlatform;
+
+/// Limits how far free platforms can move, configured per level
+#[derive(Debug, Clone)]
+pub struct Fuel {
+    pub capacity: f32,
+    /// Fuel burned per second while any movement key is held
+    pub burn_rate: f32,
+    /// Fuel gained per second while resting on the landing pad
+    pub refuel_rate: f32,
+    /// Fee in $ for every unit of fuel burned
+    pub price: f32,
+    pub landing_pad: Vec2,
+}
+
+#[derive(Component, Debug)]
+pub struct FuelTank {
+    pub fuel: f32,
+    pub capacity: f32,
+    pub burn_rate: f32,
+}
+
+impl FuelTank {
+    /// Burns up to `amount` of fuel, returns how much was actually burned
+    pub fn burn(&mut self, amount: f32) -> f32 {
+        let burned = amount.min(self.fuel);
+        self.fuel -= burned;
+        burned
+    }
+
+    pub fn ratio(&self) -> f32 {
+        self.fuel / self.capacity
+    }
+}
+
+#[derive(Component, Debug)]
+pub struct LandingPad;
+
+const LANDING_PAD_SIZE: Vec2 = Vec2::new(3.0, 0.3);
+/// Platforms have to be this close to the pad and almost stopped to refuel
+const LANDING_PAD_RANGE: f32 = 1.5;
 
 #[derive(Component, Debug)]
 pub struct Barrel;
@@ -38,11 +121,9 @@ pub struct PlatformBase;
 
 pub fn spawn_launch_platform_system(
     mut commands: Commands,
-    mut assets: ResMut<AssetServer>,
+    assets: ResMut<AssetServer>,
     level: Res<Level>,
 ) {
-    let collider = Collider::cuboid(100.0, 10.0);
-
     let res_w = 370.0;
     let res_h = 255.0;
     let res_h_on = 490.0;
@@ -54,20 +135,63 @@ pub fn spawn_launch_platform_system(
 
     let barrel_size = Vec2::new(barrel_res_w * ASSET_SCALE, barrel_res_h * ASSET_SCALE);
 
-    commands
-        .spawn((
-            LaunchPlatform,
+    if let Some(fuel) = &level.fuel {
+        commands.spawn((
+            LandingPad,
             LevelLifecycle,
-            SpatialBundle::from(Transform::from_translation(Vec3::from((
-                level.launch_platform.translation,
-                0.0,
-            )))),
-            RigidBody::KinematicVelocityBased,
-            Velocity::zero(),
-            //collider,
-        ))
-        .with_children(|parent| {
-            if let LaunchPlatformKind::Static = level.launch_platform.kind {
+            SpriteBundle {
+                transform: Transform::from_xyz(fuel.landing_pad.x, fuel.landing_pad.y, -0.1),
+                sprite: Sprite {
+                    color: Color::rgb(0.9, 0.55, 0.1),
+                    custom_size: Some(LANDING_PAD_SIZE),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ));
+    }
+
+    for (index, config) in level.launch_platforms.iter().enumerate() {
+        let platform = commands
+            .spawn((
+                LaunchPlatform {
+                    index,
+                    kind: config.kind.clone(),
+                },
+                LevelLifecycle,
+                SpatialBundle::from(Transform::from_translation(Vec3::from((
+                    config.translation,
+                    0.0,
+                )))),
+                RigidBody::KinematicVelocityBased,
+                Velocity::zero(),
+                //collider,
+            ))
+            .id();
+
+        if index == 0 {
+            commands.entity(platform).insert(ActiveLaunchPlatform);
+        }
+
+        if let LaunchPlatformKind::Free = config.kind {
+            commands.entity(platform).insert(Recoil::default());
+        }
+
+        if let (LaunchPlatformKind::Free, Some(fuel)) = (&config.kind, &level.fuel) {
+            commands.entity(platform).insert(FuelTank {
+                fuel: fuel.capacity,
+                capacity: fuel.capacity,
+                burn_rate: fuel.burn_rate,
+            });
+        }
+
+        if let LaunchPlatformKind::Crane { min_x, max_x } = config.kind {
+            spawn_crane(&mut commands, platform, config.translation, min_x, max_x);
+            continue;
+        }
+
+        commands.entity(platform).with_children(|parent| {
+            if let LaunchPlatformKind::Static = config.kind {
                 parent.spawn((
                     PlatformBase,
                     SpriteBundle {
@@ -116,20 +240,35 @@ pub fn spawn_launch_platform_system(
                 },
             ));
         });
+    }
 }
 
 pub fn launch_platform_control_system(
     mut commands: Commands,
     key_code: Res<Input<KeyCode>>,
-    mut query: Query<&mut Velocity, With<LaunchPlatform>>,
-    mut base_query: Query<(Entity, &mut Transform), With<PlatformBase>>,
-    level: Res<Level>,
+    mut query: Query<
+        (&mut Velocity, &mut Recoil, Option<&mut FuelTank>),
+        With<ActiveLaunchPlatform>,
+    >,
+    platform_query: Query<(&LaunchPlatform, &Children), With<ActiveLaunchPlatform>>,
+    base_query: Query<(Entity, &Transform), With<PlatformBase>>,
+    mut level_stats: ResMut<LevelStats>,
+    time: Res<Time>,
 ) {
-    if let LaunchPlatformKind::Static = level.launch_platform.kind {
+    let Ok((platform, children)) = platform_query.get_single() else {
+        return;
+    };
+
+    if !matches!(platform.kind, LaunchPlatformKind::Free) {
         return;
     }
 
-    let (base, mut base_transform) = base_query.get_single_mut().unwrap();
+    let Some((base, base_transform)) = children
+        .iter()
+        .find_map(|child| base_query.get(*child).ok())
+    else {
+        return;
+    };
 
     let mut tween = |end: f32| {
         let tween = Tween::new(
@@ -143,18 +282,32 @@ pub fn launch_platform_control_system(
         commands.entity(base).insert(Animator::new(tween));
     };
 
-    for mut velocity in query.iter_mut() {
+    for (mut velocity, mut recoil, fuel_tank) in query.iter_mut() {
         let max_velocity = 25.0;
         let increment = 0.5;
         let decrement = 0.5;
 
-        /// WASD for launch platform
-        if key_code.pressed(KeyCode::A) {
+        // Only the thrust is braked, the recoil is added back below
+        velocity.linvel -= recoil.0;
+
+        let thrusting = key_code.any_pressed([KeyCode::W, KeyCode::A, KeyCode::S, KeyCode::D]);
+        let has_fuel = match fuel_tank {
+            Some(mut fuel_tank) if thrusting => {
+                let amount = fuel_tank.burn_rate * time.delta_seconds();
+                let burned = fuel_tank.burn(amount);
+                level_stats.fuel_used += burned;
+                burned > 0.0
+            }
+            _ => true,
+        };
+
+        // WASD for launch platform
+        if has_fuel && key_code.pressed(KeyCode::A) {
             velocity.linvel.x -= increment;
             if velocity.linvel.x < -max_velocity {
                 velocity.linvel.x = -max_velocity;
             }
-        } else if key_code.pressed(KeyCode::D) {
+        } else if has_fuel && key_code.pressed(KeyCode::D) {
             velocity.linvel.x += increment;
             if velocity.linvel.x > max_velocity {
                 velocity.linvel.x = max_velocity;
@@ -171,12 +324,12 @@ pub fn launch_platform_control_system(
             }
         }
 
-        if key_code.pressed(KeyCode::W) {
+        if has_fuel && key_code.pressed(KeyCode::W) {
             velocity.linvel.y += increment;
             if velocity.linvel.y > max_velocity {
                 velocity.linvel.y = max_velocity;
             }
-        } else if key_code.pressed(KeyCode::S) {
+        } else if has_fuel && key_code.pressed(KeyCode::S) {
             velocity.linvel.y -= increment;
             if velocity.linvel.y < -max_velocity {
                 velocity.linvel.y = -max_velocity;
@@ -192,6 +345,9 @@ pub fn launch_platform_control_system(
                 velocity.linvel.y = 0.0;
             }
         }
+
+        recoil.fade(time.delta_seconds());
+        velocity.linvel += recoil.0;
     }
 
     if key_code.just_pressed(KeyCode::D) {
@@ -203,25 +359,149 @@ pub fn launch_platform_control_system(
     }
 }
 
-pub fn barrel_rotation_system(aim: Res<Aim>, mut query: Query<&mut Transform, With<Barrel>>) {
-    let barrel = query.get_single_mut();
+pub fn barrel_rotation_system(
+    aim: Res<Aim>,
+    platform_query: Query<&Children, With<ActiveLaunchPlatform>>,
+    mut query: Query<&mut Transform, With<Barrel>>,
+) {
+    for children in platform_query.iter() {
+        for child in children.iter() {
+            if let Ok(mut barrel) = query.get_mut(*child) {
+                barrel.rotation = Quat::from_rotation_z(
+                    -aim.barrel_direction
+                        .unwrap_or(Vec2::Y)
+                        .angle_between(Vec2::Y),
+                );
+            }
+        }
+    }
+}
 
-    if let Ok(mut barrel) = barrel {
-        barrel.rotation = Quat::from_rotation_z(
-            -aim.barrel_direction
-                .unwrap_or(Vec2::Y)
-                .angle_between(Vec2::Y),
-        );
+pub fn refuel_system(
+    mut query: Query<(&Transform, &Velocity, &mut FuelTank)>,
+    level: Res<Level>,
+    time: Res<Time>,
+) {
+    let Some(fuel) = &level.fuel else {
+        return;
+    };
+
+    for (transform, velocity, mut fuel_tank) in query.iter_mut() {
+        let on_pad = transform.translation.xy().distance(fuel.landing_pad) < LANDING_PAD_RANGE;
+        if on_pad && velocity.linvel.length() < 0.5 {
+            fuel_tank.fuel =
+                (fuel_tank.fuel + fuel.refuel_rate * time.delta_seconds()).min(fuel_tank.capacity);
+        }
     }
 }
 
-pub fn keep_launch_platform_visible_system(mut query: Query<&mut Transform, With<LaunchPlatform>>) {
-    for mut transform in query.iter_mut() {
-        transform.translation.x = transform.translation.x.clamp(
+/// Tab cycles through the launch platforms of the level
+pub fn switch_launch_platform_system(
+    mut commands: Commands,
+    key_code: Res<Input<KeyCode>>,
+    mut query: Query<(
+        Entity,
+        &LaunchPlatform,
+        &mut Velocity,
+        Option<&mut Recoil>,
+        &Children,
+        Has<ActiveLaunchPlatform>,
+    )>,
+    base_query: Query<(Entity, &Transform), With<PlatformBase>>,
+) {
+    if !key_code.just_pressed(KeyCode::Tab) {
+        return;
+    }
+
+    let count = query.iter().count();
+    let Some(active) = query
+        .iter()
+        .find(|(_, _, _, _, _, is_active)| *is_active)
+        .map(|(_, platform, _, _, _, _)| platform.index)
+    else {
+        return;
+    };
+
+    if count < 2 {
+        return;
+    }
+
+    let next = (active + 1) % count;
+    for (entity, platform, mut velocity, recoil, children, is_active) in query.iter_mut() {
+        if is_active {
+            // Don't leave the previous platform drifting or tilted
+            velocity.linvel = Vec2::ZERO;
+            if let Some(mut recoil) = recoil {
+                recoil.0 = Vec2::ZERO;
+            }
+
+            if let Some((base, base_transform)) = children
+                .iter()
+                .find_map(|child| base_query.get(*child).ok())
+            {
+                let tween = Tween::new(
+                    EaseFunction::QuadraticInOut,
+                    Duration::from_secs_f32(0.25),
+                    TransformRotationLens {
+                        start: base_transform.rotation,
+                        end: Quat::IDENTITY,
+                    },
+                );
+                commands.entity(base).insert(Animator::new(tween));
+            }
+
+            commands.entity(entity).remove::<ActiveLaunchPlatform>();
+        } else if platform.index == next {
+            commands.entity(entity).insert(ActiveLaunchPlatform);
+        }
+    }
+}
+
+/// Dims the cannons that are not controlled right now
+pub fn highlight_active_launch_platform_system(
+    platform_query: Query<(&Children, Has<ActiveLaunchPlatform>), With<LaunchPlatform>>,
+    mut sprite_query: Query<&mut Sprite, Or<(With<PlatformBase>, With<Barrel>)>>,
+) {
+    for (children, is_active) in platform_query.iter() {
+        let color = if is_active {
+            Color::WHITE
+        } else {
+            Color::rgb(0.5, 0.5, 0.5)
+        };
+
+        for child in children.iter() {
+            if let Ok(mut sprite) = sprite_query.get_mut(*child) {
+                sprite.color = color;
+            }
+        }
+    }
+}
+
+pub fn keep_launch_platform_visible_system(
+    mut query: Query<(&mut Transform, &mut Velocity, Option<&mut Recoil>), With<LaunchPlatform>>,
+) {
+    for (mut transform, mut velocity, mut recoil) in query.iter_mut() {
+        let x = transform.translation.x.clamp(
             -HORIZONTAL_VIEWPORT_SIZE / 2.0,
             HORIZONTAL_VIEWPORT_SIZE / 2.0,
         );
+        let y = transform.translation.y.max(10.0);
+
+        // Stop pushing against the edge, otherwise the platform keeps a velocity it doesn't move with
+        if x != transform.translation.x {
+            velocity.linvel.x = 0.0;
+            if let Some(recoil) = recoil.as_mut() {
+                recoil.0.x = 0.0;
+            }
+        }
+        if y != transform.translation.y {
+            velocity.linvel.y = 0.0;
+            if let Some(recoil) = recoil.as_mut() {
+                recoil.0.y = 0.0;
+            }
+        }
 
-        transform.translation.y = transform.translation.y.max(10.0);
+        transform.translation.x = x;
+        transform.translation.y = y;
     }
 }
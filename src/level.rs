@@ -1,13 +1,29 @@
 use std::time::Duration;
 
 use bevy::prelude::*;
-use bevy_rapier2d::prelude::Velocity;
+use bevy_rapier2d::prelude::{RapierConfiguration, RapierContext, TimestepMode, Velocity};
 
-use crate::base::BaseType;
+use crate::base::{BaseMount, BaseMovement, BaseStep, BaseType};
 use crate::block::{Aiming, Block, Falling};
-use crate::effect::EffectType;
-use crate::level_intro_dialog::DialogResource;
+use crate::effect::anchor::ANCHOR;
+use crate::effect::balloon::BALLOON;
+use crate::effect::explosive::EXPLOSIVE;
+use crate::effect::glue::GLUE;
+use crate::effect::ice::ICE;
+use crate::effect::lightning_rod::LIGHTNING_ROD;
+use crate::effect::magnetic::{MAGNET_NORTH, MAGNET_SOUTH};
+use crate::effect::platform::PLATFORM;
+use crate::effect::rubber::RUBBER;
+use crate::effect::EffectId;
+use crate::environment::earthquake::{Earthquake, EarthquakeTrigger};
+use crate::environment::flood::Flood;
+use crate::environment::flying_obstacle::{FlyingObstacleType, FlyingObstacles};
+use crate::environment::lightning::Lightning;
+use crate::environment::wind::WindPattern;
+use crate::environment::wrecking_ball::WreckingBall;
+use crate::launch_platform::Fuel;
 use crate::state::LevelState;
+use crate::GRAVITY;
 
 pub struct LevelPlugin;
 
@@ -25,7 +41,10 @@ impl Plugin for LevelPlugin {
                     .run_if(in_state(LevelState::Playing)),
             ),
         )
-        .add_systems(OnEnter(LevelState::Playing), reset_level_stats)
+        .add_systems(
+            OnEnter(LevelState::Playing),
+            (reset_level_stats, apply_level_physics),
+        )
         .init_resource::<LevelStats>()
         .insert_resource(LEVELS[0].clone())
         .add_event::<NextLevel>()
@@ -42,6 +61,10 @@ pub struct LevelStats {
 
     pub cars_hit: usize,
 
+    pub blocks_demolished: usize,
+
+    pub fuel_used: f32,
+
     pub timer: Option<Timer>,
 }
 
@@ -50,6 +73,7 @@ pub enum UpdateLevelStats {
     BlockThrown,
     BlockDestroyed,
     CarHit,
+    BlockDemolished,
 }
 
 #[derive(Debug, Clone)]
@@ -57,12 +81,21 @@ pub struct LevelBase {
     pub base_type: BaseType,
     pub translation: Vec2,
     pub rotation: f32,
+    pub movement: BaseMovement,
+    pub mount: BaseMount,
+    /// Mass in kg the base can carry before it gives way
+    pub max_load: Option<f32>,
 }
 
 #[derive(Debug, Clone)]
 pub enum LaunchPlatformKind {
     Static,
     Free,
+    /// Trolley on a beam at the platform height, drops blocks from a hook
+    Crane {
+        min_x: f32,
+        max_x: f32,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -92,6 +125,9 @@ const fn default_level_base() -> LevelBase {
         base_type: BaseType::T7,
         translation: Vec2::new(0.0, DEFAULT_BASE_HEIGHT),
         rotation: 0.0,
+        movement: BaseMovement::Static,
+        mount: BaseMount::Fixed,
+        max_load: None,
     }
 }
 
@@ -101,20 +137,64 @@ pub struct Level {
     pub name: &'static str,
     pub goal: LevelGoal,
     pub max_blocks: Option<usize>,
+    #[allow(dead_code)]
     pub time_limit: Option<Duration>,
     pub bases: &'static [LevelBase],
-    pub enabled_effects: &'static [(EffectType, f32)],
+    pub enabled_effects: &'static [(EffectId, f32)],
     pub effect_likelihood: f32,
+    /// How many effects a single block can roll, each with `effect_likelihood`
+    pub max_effects: usize,
     pub intro_text: Option<&'static str>,
     pub rain: Option<usize>,
+    pub snow: Option<usize>,
+    pub lightning: Option<Lightning>,
+    pub flying_obstacles: Option<FlyingObstacles>,
+    pub wind: Option<WindPattern>,
+    pub earthquakes: &'static [Earthquake],
+    pub flood: Option<Flood>,
+    pub wrecking_ball: Option<WreckingBall>,
     pub friction: f32,
-    pub launch_platform: LaunchPlatform,
+    /// The first platform is active when the level starts, Tab switches between them
+    pub launch_platforms: &'static [LaunchPlatform],
+    pub fuel: Option<Fuel>,
+    pub physics: LevelPhysics,
+}
+
+#[derive(Debug, Clone)]
+pub struct LevelPhysics {
+    /// Vertical acceleration, negative values pull down
+    pub gravity: f32,
+    pub substeps: usize,
+    pub velocity_iterations: usize,
+    pub friction_iterations: usize,
 }
 
-pub const DEFAULT_EFFECTS: [(EffectType, f32); 2] =
-    [(EffectType::Glue, 1.0), (EffectType::Magnetic, 1.0)];
+impl LevelPhysics {
+    pub fn gravity(&self) -> Vec2 {
+        Vec2::Y * self.gravity
+    }
+}
+
+/// Rapier's defaults for the solver
+pub const DEFAULT_PHYSICS: LevelPhysics = LevelPhysics {
+    gravity: GRAVITY,
+    substeps: 1,
+    velocity_iterations: 4,
+    friction_iterations: 8,
+};
+
+pub const DEFAULT_EFFECTS: [(EffectId, f32); 8] = [
+    (GLUE, 1.0),
+    (MAGNET_NORTH, 0.5),
+    (MAGNET_SOUTH, 0.5),
+    (BALLOON, 0.5),
+    (EXPLOSIVE, 0.25),
+    (ANCHOR, 0.5),
+    (ICE, 0.5),
+    (RUBBER, 0.5),
+];
 
-pub const NO_EFFECTS: [(EffectType, f32); 0] = [];
+pub const NO_EFFECTS: [(EffectId, f32); 0] = [];
 
 pub const DEFAULT_LEVEL: Level = Level {
     level: 0,
@@ -128,10 +208,20 @@ pub const DEFAULT_LEVEL: Level = Level {
     }],
     enabled_effects: &DEFAULT_EFFECTS,
     effect_likelihood: 0.05,
+    max_effects: 1,
     intro_text: None,
     rain: None,
+    snow: None,
+    lightning: None,
+    flying_obstacles: None,
+    wind: None,
+    earthquakes: &[],
+    flood: None,
+    wrecking_ball: None,
     friction: 0.5,
-    launch_platform: static_launch_platform(),
+    launch_platforms: &[static_launch_platform()],
+    fuel: None,
+    physics: DEFAULT_PHYSICS,
 };
 
 #[derive(Debug, Clone)]
@@ -146,7 +236,7 @@ pub struct NextLevel(pub Option<usize>);
 #[derive(Component, Debug, Clone)]
 pub struct LevelLifecycle;
 
-pub static LEVELS: [Level; 10] = [
+pub static LEVELS: [Level; 27] = [
     Level {
         level: 0,
         name: "First day on the job",
@@ -193,10 +283,10 @@ Q / E: Finely adjust rotation
         rain: Some(10),
         friction: 0.2,
         enabled_effects: &NO_EFFECTS,
-        launch_platform: LaunchPlatform {
+        launch_platforms: &[LaunchPlatform {
           translation: Vec2::new(13.0, 10.5),
             kind: LaunchPlatformKind::Static,
-        },
+        }],
         ..DEFAULT_LEVEL
     },
     Level {
@@ -214,7 +304,7 @@ Q / E: Finely adjust rotation
             base_type: BaseType::T3,
             ..default_level_base()
         }],
-        enabled_effects: &[(EffectType::Glue, 1.0)],
+        enabled_effects: &[(GLUE, 1.0)],
         effect_likelihood: 0.1,
         ..DEFAULT_LEVEL
     },
@@ -231,8 +321,8 @@ Q / E: Finely adjust rotation
                 ..default_level_base()
             },
         ],
-        enabled_effects: &[(EffectType::Glue, 1.0)],
-        launch_platform: free_launch_platform(),
+        enabled_effects: &[(GLUE, 1.0)],
+        launch_platforms: &[free_launch_platform()],
         ..DEFAULT_LEVEL
     },
     Level {
@@ -246,8 +336,8 @@ Q / E: Finely adjust rotation
                 ..default_level_base()
             },
         ],
-        enabled_effects: &[(EffectType::Glue, 1.0)],
-        launch_platform: free_launch_platform(),
+        enabled_effects: &[(GLUE, 1.0)],
+        launch_platforms: &[free_launch_platform()],
         rain: Some(10),
         friction: 0.2,
         ..DEFAULT_LEVEL
@@ -255,7 +345,8 @@ Q / E: Finely adjust rotation
     Level {
         level: 6,
         name: "Attraction",
-        intro_text: Some("We've ordered some magnets, these should hopefully help with building stability."),
+        intro_text: Some("We've ordered some magnets, these should hopefully help with building stability.
+Careful: opposite poles attract each other, equal poles repel!"),
         goal: LevelGoal::ReachHeight(30.0),
         bases: &[
             LevelBase {
@@ -264,11 +355,13 @@ Q / E: Finely adjust rotation
             },
         ],
         enabled_effects: &[
-            (EffectType::Glue, 1.0),
-            (EffectType::Magnetic, 2.0),
+            (GLUE, 1.0),
+            (MAGNET_NORTH, 1.0),
+            (MAGNET_SOUTH, 1.0),
         ],
         effect_likelihood: 0.1,
-        launch_platform: free_launch_platform(),
+        max_effects: 2,
+        launch_platforms: &[free_launch_platform()],
         ..DEFAULT_LEVEL
     },
 
@@ -289,7 +382,7 @@ Q / E: Finely adjust rotation
                 ..default_level_base()
             },
         ],
-        launch_platform: free_launch_platform(),
+        launch_platforms: &[free_launch_platform()],
         ..DEFAULT_LEVEL
     },
     Level {
@@ -304,7 +397,7 @@ Q / E: Finely adjust rotation
                 ..default_level_base()
             },
         ],
-        launch_platform: free_launch_platform(),
+        launch_platforms: &[free_launch_platform()],
         rain: Some(10),
         friction: 0.2,
         ..DEFAULT_LEVEL
@@ -327,13 +420,373 @@ Q / E: Finely adjust rotation
                 ..default_level_base()
             },
         ],
-        launch_platform: free_launch_platform(),
+        launch_platforms: &[free_launch_platform()],
+        ..DEFAULT_LEVEL
+    },
+    Level {
+        level: 10,
+        name: "Moving Day",
+        intro_text: Some("This building is on wheels! Move the base with J and L to catch your blocks.
+Blue platform blocks lock onto the base and travel with it."),
+        goal: LevelGoal::ReachHeight(15.0),
+        bases: &[
+            LevelBase {
+                base_type: BaseType::T4,
+                movement: BaseMovement::Keyboard { max_speed: 6.0 },
+                ..default_level_base()
+            },
+        ],
+        enabled_effects: &[(PLATFORM, 1.0)],
+        effect_likelihood: 0.2,
+        ..DEFAULT_LEVEL
+    },
+    Level {
+        level: 11,
+        name: "Conveyor Belt",
+        intro_text: Some("The base is riding along a conveyor belt, time your throws well. Platform blocks will keep your tower on track."),
+        goal: LevelGoal::ReachHeight(15.0),
+        bases: &[
+            LevelBase {
+                base_type: BaseType::T4,
+                movement: BaseMovement::Path {
+                    waypoints: &[Vec2::new(-6.0, 0.0), Vec2::new(6.0, 0.0)],
+                    speed: 2.0,
+                },
+                ..default_level_base()
+            },
+        ],
+        enabled_effects: &[(PLATFORM, 1.0), (GLUE, 1.0)],
+        effect_likelihood: 0.2,
+        launch_platforms: &[free_launch_platform()],
+        ..DEFAULT_LEVEL
+    },
+    Level {
+        level: 12,
+        name: "Windy City",
+        intro_text: Some("A storm is coming in. The wind will push your blocks around mid-air, keep an eye on the wind indicator."),
+        goal: LevelGoal::ReachHeight(18.0),
+        bases: &[
+            LevelBase {
+                base_type: BaseType::T7,
+                ..default_level_base()
+            },
+        ],
+        wind: Some(WindPattern::Changing {
+            strength: 4.0,
+            period: 30.0,
+        }),
+        launch_platforms: &[free_launch_platform()],
+        ..DEFAULT_LEVEL
+    },
+    Level {
+        level: 13,
+        name: "Fault Line",
+        intro_text: Some("The city is built on a fault line. Expect the ground to shake, glue your tower together before it does!"),
+        goal: LevelGoal::ReachHeight(20.0),
+        bases: &[
+            LevelBase {
+                base_type: BaseType::T7,
+                ..default_level_base()
+            },
+        ],
+        enabled_effects: &[(GLUE, 1.0), (ANCHOR, 0.5)],
+        effect_likelihood: 0.2,
+        earthquakes: &[
+            Earthquake {
+                trigger: EarthquakeTrigger::AfterSeconds(20.0),
+                amplitude: 0.15,
+                frequency: 2.0,
+                duration: 4.0,
+            },
+            Earthquake {
+                trigger: EarthquakeTrigger::AtHeight(14.0),
+                amplitude: 0.25,
+                frequency: 1.5,
+                duration: 6.0,
+            },
+        ],
+        launch_platforms: &[free_launch_platform()],
+        ..DEFAULT_LEVEL
+    },
+    Level {
+        level: 14,
+        name: "High Tide",
+        intro_text: Some("The river is flooding the city! Build fast, the water swallows every block it reaches."),
+        goal: LevelGoal::ReachHeight(15.0),
+        bases: &[
+            LevelBase {
+                base_type: BaseType::T7,
+                ..default_level_base()
+            },
+        ],
+        flood: Some(Flood {
+            start_height: 5.0,
+            // Stops just below the top of the base so the tower itself stays dry
+            max_height: DEFAULT_BASE_HEIGHT - 1.0,
+            rise_speed: 0.08,
+            delay: 10.0,
+        }),
+        launch_platforms: &[free_launch_platform()],
+        ..DEFAULT_LEVEL
+    },
+    Level {
+        level: 15,
+        name: "Moon Construction",
+        intro_text: Some("Our first off-world contract! Gravity on the moon is only a sixth of what you're used to, so blocks fly a lot further and settle slowly."),
+        goal: LevelGoal::ReachHeight(20.0),
+        bases: &[
+            LevelBase {
+                base_type: BaseType::T7,
+                ..default_level_base()
+            },
+        ],
+        physics: LevelPhysics {
+            gravity: GRAVITY / 6.0,
+            // Light blocks bounce around a lot, more iterations keep the tower from jittering
+            substeps: 2,
+            velocity_iterations: 8,
+            friction_iterations: 16,
+        },
+        launch_platforms: &[free_launch_platform()],
+        ..DEFAULT_LEVEL
+    },
+    Level {
+        level: 16,
+        name: "Winter Wonderland",
+        intro_text: Some("It's snowing! Snow piles up on your tower, making it heavier and slippery. A good bump shakes it off again."),
+        goal: LevelGoal::ReachHeight(16.0),
+        bases: &[
+            LevelBase {
+                base_type: BaseType::T7,
+                ..default_level_base()
+            },
+        ],
+        snow: Some(4),
+        friction: 0.4,
+        launch_platforms: &[free_launch_platform()],
+        ..DEFAULT_LEVEL
+    },
+    Level {
+        level: 17,
+        name: "Thunderstruck",
+        intro_text: Some("A thunderstorm is raging. Lightning strikes the tallest block of your tower, unless you have a lightning rod block up there to catch it."),
+        goal: LevelGoal::ReachHeight(18.0),
+        bases: &[
+            LevelBase {
+                base_type: BaseType::T7,
+                ..default_level_base()
+            },
+        ],
+        rain: Some(10),
+        friction: 0.3,
+        lightning: Some(Lightning {
+            interval: 12.0,
+            force: 15.0,
+            debris_chance: 0.3,
+        }),
+        enabled_effects: &[(LIGHTNING_ROD, 1.0), (GLUE, 0.5)],
+        effect_likelihood: 0.15,
+        launch_platforms: &[free_launch_platform()],
+        ..DEFAULT_LEVEL
+    },
+    Level {
+        level: 18,
+        name: "Busy Skies",
+        intro_text: Some("The tower is getting in the way of air traffic. Birds, drones and even helicopters will knock your blocks off course, time your throws."),
+        goal: LevelGoal::ReachHeight(20.0),
+        bases: &[
+            LevelBase {
+                base_type: BaseType::T7,
+                ..default_level_base()
+            },
+        ],
+        flying_obstacles: Some(FlyingObstacles {
+            types: &[
+                FlyingObstacleType::Bird,
+                FlyingObstacleType::Bird,
+                FlyingObstacleType::Drone,
+                FlyingObstacleType::Helicopter,
+            ],
+            interval: 6.0,
+            min_height: 15.0,
+            max_height: 30.0,
+            nudge_settled: true,
+        }),
+        wind: Some(WindPattern::Gusting {
+            strength: 2.0,
+            period: 8.0,
+        }),
+        launch_platforms: &[free_launch_platform()],
+        ..DEFAULT_LEVEL
+    },
+    Level {
+        level: 19,
+        name: "Demolition Site",
+        intro_text: Some("The building next door is being torn down. Keep your tower out of the wrecking ball's path, the red arc shows where it swings."),
+        goal: LevelGoal::ReachHeight(20.0),
+        bases: &[
+            LevelBase {
+                base_type: BaseType::T7,
+                ..default_level_base()
+            },
+        ],
+        wrecking_ball: Some(WreckingBall {
+            // Only the right edge of the base is in reach of the ball
+            pivot: Vec2::new(14.0, 34.0),
+            length: 16.0,
+            radius: 1.2,
+            release_angle: 0.45,
+            interval: 6.0,
+        }),
+        launch_platforms: &[free_launch_platform()],
+        ..DEFAULT_LEVEL
+    },
+    Level {
+        level: 20,
+        name: "Balancing Act",
+        intro_text: Some("This base is resting on a pivot. Keep the weight balanced or your tower will slide off."),
+        goal: LevelGoal::ReachHeight(12.0),
+        bases: &[
+            LevelBase {
+                base_type: BaseType::T7,
+                mount: BaseMount::Seesaw { max_angle: 0.35 },
+                ..default_level_base()
+            },
+        ],
+        ..DEFAULT_LEVEL
+    },
+    Level {
+        level: 21,
+        name: "Merry-Go-Round",
+        intro_text: Some("The base slowly tilts back and forth. Build a tower that can take the swaying."),
+        goal: LevelGoal::ReachHeight(12.0),
+        bases: &[
+            LevelBase {
+                base_type: BaseType::T7,
+                mount: BaseMount::Turntable {
+                    angular_speed: 0.1,
+                    max_angle: 0.25,
+                },
+                ..default_level_base()
+            },
+        ],
+        ..DEFAULT_LEVEL
+    },
+    Level {
+        level: 22,
+        name: "Rooftops",
+        intro_text: Some("The roof of this building is anything but flat. Use the steps to your advantage."),
+        goal: LevelGoal::ReachHeight(15.0),
+        bases: &[
+            LevelBase {
+                base_type: BaseType::Procedural {
+                    width: 12.0,
+                    steps: &[
+                        BaseStep {
+                            x: -4.5,
+                            width: 3.0,
+                            height: 2.0,
+                        },
+                        BaseStep {
+                            x: 3.0,
+                            width: 2.0,
+                            height: 1.0,
+                        },
+                        BaseStep {
+                            x: 5.0,
+                            width: 2.0,
+                            height: 3.0,
+                        },
+                    ],
+                },
+                ..default_level_base()
+            },
+        ],
+        wind: Some(WindPattern::Constant(1.5)),
+        ..DEFAULT_LEVEL
+    },
+    Level {
+        level: 23,
+        name: "Weak Foundations",
+        intro_text: Some("This old building can't carry much. Watch the load gauge, an overloaded base starts to sink."),
+        goal: LevelGoal::ReachHeight(12.0),
+        bases: &[
+            LevelBase {
+                base_type: BaseType::T4,
+                max_load: Some(40.0),
+                ..default_level_base()
+            },
+        ],
+        ..DEFAULT_LEVEL
+    },
+    Level {
+        level: 24,
+        name: "Sky Crane",
+        intro_text: Some("No cannon today. Move the crane with A/D, lower the hook with W/S and click to drop the block."),
+        goal: LevelGoal::ReachHeight(10.0),
+        bases: &[
+            LevelBase {
+                base_type: BaseType::T4,
+                ..default_level_base()
+            },
+        ],
+        launch_platforms: &[LaunchPlatform {
+            translation: Vec2::new(8.0, 26.0),
+            kind: LaunchPlatformKind::Crane {
+                min_x: -12.0,
+                max_x: 12.0,
+            },
+        }],
+        ..DEFAULT_LEVEL
+    },
+    Level {
+        level: 25,
+        name: "Crossfire",
+        intro_text: Some("There is a cannon on each side of the site now. Press Tab to switch between them."),
+        goal: LevelGoal::ReachHeight(15.0),
+        bases: &[
+            LevelBase {
+                base_type: BaseType::T9,
+                ..default_level_base()
+            },
+        ],
+        wind: Some(WindPattern::Changing {
+            strength: 3.0,
+            period: 40.0,
+        }),
+        launch_platforms: &[
+            free_launch_platform(),
+            LaunchPlatform {
+                translation: Vec2::new(-14.3, 14.8),
+                kind: LaunchPlatformKind::Free,
+            },
+        ],
+        ..DEFAULT_LEVEL
+    },
+    Level {
+        level: 26,
+        name: "Running on Fumes",
+        intro_text: Some("Fuel is expensive. Every move of the cannon burns some, land on the orange pad to refuel."),
+        goal: LevelGoal::ReachHeight(15.0),
+        bases: &[
+            LevelBase {
+                base_type: BaseType::T7,
+                ..default_level_base()
+            },
+        ],
+        launch_platforms: &[free_launch_platform()],
+        fuel: Some(Fuel {
+            capacity: 10.0,
+            burn_rate: 1.0,
+            refuel_rate: 2.0,
+            price: 50.0,
+            landing_pad: Vec2::new(18.0, 12.0),
+        }),
         ..DEFAULT_LEVEL
     },
 ];
 
 pub fn load_level_event(
-    mut commands: Commands,
     mut evr: EventReader<NextLevel>,
     mut level_stats: ResMut<LevelStats>,
     mut level_state: ResMut<NextState<LevelState>>,
@@ -355,6 +808,26 @@ pub fn load_level_event(
     }
 }
 
+pub fn is_settled(velocity: &Velocity) -> bool {
+    velocity.linvel.length() < 0.03
+}
+
+/// Height of the highest corner of the block
+pub fn block_top(block: &Block, transform: &Transform) -> f32 {
+    let corners = block.block_type.all_corners();
+
+    corners
+        .iter()
+        .map(|corner| {
+            let pos = transform
+                .compute_matrix()
+                .transform_point(Vec3::from((*corner, 0.0)));
+            pos.y
+        })
+        .max_by(|a, b| a.partial_cmp(b).unwrap())
+        .unwrap_or(0.0)
+}
+
 pub fn check_current_block_stats(
     query: Query<(&Block, &Transform, &Velocity), (Without<Aiming>, Without<Falling>)>,
     level: Res<Level>,
@@ -371,23 +844,10 @@ pub fn check_current_block_stats(
         .unwrap_or(0.0);
 
     for (block, transform, velocity) in query.iter() {
-        if velocity.linvel.length() < 0.03 {
+        if is_settled(velocity) {
             block_count += 1;
 
-            let corners = block.block_type.all_corners();
-
-            let height = (corners
-                .iter()
-                .map(|corner| {
-                    let pos = transform
-                        .compute_matrix()
-                        .transform_point(Vec3::from((*corner, 0.0)));
-                    pos.y
-                })
-                .max_by(|a, b| a.partial_cmp(b).unwrap())
-                .unwrap_or(0.0)
-                - base_height)
-                .max(0.0);
+            let height = (block_top(block, transform) - base_height).max(0.0);
 
             if height > max_height {
                 max_height = height;
@@ -400,7 +860,6 @@ pub fn check_current_block_stats(
 }
 
 pub fn check_win_loose_condition(
-    mut commands: Commands,
     mut level_stats: ResMut<LevelStats>,
     level: Res<Level>,
     mut state: ResMut<NextState<LevelState>>,
@@ -428,12 +887,27 @@ pub fn check_win_loose_condition(
     }
 }
 
+pub fn apply_level_physics(
+    level: Res<Level>,
+    mut config: ResMut<RapierConfiguration>,
+    mut context: ResMut<RapierContext>,
+) {
+    config.gravity = level.physics.gravity();
+    if let TimestepMode::Fixed { substeps, .. } = &mut config.timestep_mode {
+        *substeps = level.physics.substeps;
+    }
+
+    context.integration_parameters.max_velocity_iterations = level.physics.velocity_iterations;
+    context
+        .integration_parameters
+        .max_velocity_friction_iterations = level.physics.friction_iterations;
+}
+
 fn reset_level_stats(mut level_stats: ResMut<LevelStats>) {
     *level_stats = LevelStats::default();
 }
 
 fn update_level_stats_events(
-    mut commands: Commands,
     mut level_stats: ResMut<LevelStats>,
     mut evr: EventReader<UpdateLevelStats>,
 ) {
@@ -448,6 +922,9 @@ fn update_level_stats_events(
             UpdateLevelStats::CarHit => {
                 level_stats.cars_hit += 1;
             }
+            UpdateLevelStats::BlockDemolished => {
+                level_stats.blocks_demolished += 1;
+            }
         }
     }
 }
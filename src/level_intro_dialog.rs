@@ -3,7 +3,7 @@ use crate::level::{Level, LevelStats, NextLevel};
 use crate::state::LevelState;
 use bevy::prelude::*;
 use bevy_egui::egui::{Color32, Frame};
-use bevy_egui::{egui, EguiContext, EguiContexts};
+use bevy_egui::{egui, EguiContexts};
 
 pub struct LevelIntroDialogPlugin;
 
@@ -70,61 +70,61 @@ pub fn update_level_intro_dialog(
         }
     }
 
-    if dialog.results_visible {
-        if *level_state == LevelState::Won || *level_state == LevelState::Lost {
-            egui::Window::new("Level Complete")
-                .collapsible(false)
-                .resizable(false)
-                .title_bar(false)
-                .anchor(egui::Align2::LEFT_CENTER, egui::Vec2::new(16.0, 0.0))
-                .constrain(true)
-                .frame(
-                    Frame::none()
-                        .fill(Color32::from_rgba_unmultiplied(255, 255, 255, 220))
-                        .inner_margin(8.0),
-                )
-                .show(egui.ctx_mut(), |ui| {
-                    ui.set_width(200.0);
-
-                    if *level_state == LevelState::Won {
-                        ui.heading("Contract Completed!");
-                        ui.label("Well Done!");
-                    } else {
-                        ui.heading("Contract Failed!");
-                        ui.label("Better luck next time!");
-                    }
-
-                    ui.add_space(50.0);
-
-                    ui.heading("Run Results");
-                    ui.label(format!("Tower Height: {:.2}", stats.current_height));
-                    ui.label(format!("Blocks Stacked: {}", stats.current_block_count));
-                    ui.label(format!("Blocks Thrown: {}", stats.blocks_thrown));
-                    ui.label(format!("Blocks Dropped: {}", stats.blocks_dropped));
-
-                    ui.heading("Damage:");
-                    ui.label(format!("Cleanup Fee: {:.2}$", fees.cleanup_fee));
-                    ui.label(format!("Property Damage: {:.2}$", fees.property_damage));
-
-                    ui.add_space(50.0);
-
-                    ui.with_layout(
-                        egui::Layout::top_down_justified(egui::Align::Center),
-                        |ui| {
-                            if ui.button("RETRY").clicked() {
-                                next_level.send(NextLevel(Some(level.level)));
-                            }
-                            if *level_state == LevelState::Won {
-                                if ui.button("NEXT").clicked() {
-                                    next_level.send(NextLevel(None));
-                                }
-                            }
-                            if ui.button("KEEP PLAYING").clicked() {
-                                set_level_state.set(LevelState::KeepPlaying);
-                            }
-                        },
-                    );
-                });
-        }
+    if dialog.results_visible
+        && (*level_state == LevelState::Won || *level_state == LevelState::Lost)
+    {
+        egui::Window::new("Level Complete")
+            .collapsible(false)
+            .resizable(false)
+            .title_bar(false)
+            .anchor(egui::Align2::LEFT_CENTER, egui::Vec2::new(16.0, 0.0))
+            .constrain(true)
+            .frame(
+                Frame::none()
+                    .fill(Color32::from_rgba_unmultiplied(255, 255, 255, 220))
+                    .inner_margin(8.0),
+            )
+            .show(egui.ctx_mut(), |ui| {
+                ui.set_width(200.0);
+
+                if *level_state == LevelState::Won {
+                    ui.heading("Contract Completed!");
+                    ui.label("Well Done!");
+                } else {
+                    ui.heading("Contract Failed!");
+                    ui.label("Better luck next time!");
+                }
+
+                ui.add_space(50.0);
+
+                ui.heading("Run Results");
+                ui.label(format!("Tower Height: {:.2}", stats.current_height));
+                ui.label(format!("Blocks Stacked: {}", stats.current_block_count));
+                ui.label(format!("Blocks Thrown: {}", stats.blocks_thrown));
+                ui.label(format!("Blocks Dropped: {}", stats.blocks_dropped));
+
+                ui.heading("Damage:");
+                ui.label(format!("Cleanup Fee: {:.2}$", fees.cleanup_fee));
+                ui.label(format!("Property Damage: {:.2}$", fees.property_damage));
+                ui.label(format!("Demolition Fee: {:.2}$", fees.demolition_fee));
+                ui.label(format!("Fuel: {:.2}$", fees.fuel_fee));
+
+                ui.add_space(50.0);
+
+                ui.with_layout(
+                    egui::Layout::top_down_justified(egui::Align::Center),
+                    |ui| {
+                        if ui.button("RETRY").clicked() {
+                            next_level.send(NextLevel(Some(level.level)));
+                        }
+                        if *level_state == LevelState::Won && ui.button("NEXT").clicked() {
+                            next_level.send(NextLevel(None));
+                        }
+                        if ui.button("KEEP PLAYING").clicked() {
+                            set_level_state.set(LevelState::KeepPlaying);
+                        }
+                    },
+                );
+            });
     }
 }
@@ -3,8 +3,11 @@ use bevy_egui::egui::load::SizedTexture;
 use bevy_egui::egui::{Color32, Frame, ImageSource, Layout, RichText};
 use bevy_egui::{egui, EguiContexts};
 
+use crate::base::BaseLoad;
 use crate::block::Aiming;
 use crate::environment::fees::LevelFees;
+use crate::environment::wind::Wind;
+use crate::launch_platform::{ActiveLaunchPlatform, FuelTank};
 use crate::level::{Level, LevelGoal, LevelStats, NextLevel, LEVELS};
 use crate::state::LevelState;
 use crate::throw::ThrowQueue;
@@ -14,7 +17,7 @@ pub struct LevelUiPlugin;
 impl Plugin for LevelUiPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(Startup, setup_level_ui)
-            .add_systems(Update, (target_ui));
+            .add_systems(Update, target_ui);
     }
 }
 
@@ -25,9 +28,10 @@ struct StatsText;
 struct RetryButton;
 
 #[derive(Component, Debug)]
+#[allow(dead_code)]
 struct NextLevelButton;
 
-pub fn setup_level_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
+pub fn setup_level_ui(mut commands: Commands) {
     commands
         .spawn(NodeBundle {
             style: Style {
@@ -56,6 +60,7 @@ pub fn setup_level_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
         });
 }
 
+#[allow(dead_code)]
 pub fn egui_level_ui(
     mut contexts: EguiContexts,
     mut next_level: EventWriter<NextLevel>,
@@ -81,6 +86,8 @@ pub fn egui_level_ui(
             ui.heading("Damage:");
             ui.label(format!("Cleanup Fee: {:.2}$", fees.cleanup_fee));
             ui.label(format!("Property Damage: {:.2}$", fees.property_damage));
+            ui.label(format!("Demolition Fee: {:.2}$", fees.demolition_fee));
+            ui.label(format!("Fuel: {:.2}$", fees.fuel_fee));
 
             ui.heading("Winning Condition:");
             match current_level.goal {
@@ -100,10 +107,8 @@ pub fn egui_level_ui(
             if ui.button("Next Level").clicked() {
                 next_level.send(NextLevel(None));
             }
-            if current_level.level > 0 {
-                if ui.button("Previous Level").clicked() {
-                    next_level.send(NextLevel(Some(current_level.level - 1)));
-                }
+            if current_level.level > 0 && ui.button("Previous Level").clicked() {
+                next_level.send(NextLevel(Some(current_level.level - 1)));
             }
             if ui.button("Retry").clicked() {
                 next_level.send(NextLevel(Some(current_level.level)));
@@ -126,11 +131,14 @@ pub fn target_ui(
     assets: Res<AssetServer>,
     mut is_initialized: Local<bool>,
     mut rendered_texture_id: Local<egui::TextureId>,
-    mut has_aiming_block: Query<(), With<Aiming>>,
+    has_aiming_block: Query<(), With<Aiming>>,
     queue: Res<ThrowQueue>,
     mut next_level: EventWriter<NextLevel>,
     mut menu_open: Local<bool>,
     level_state: Res<State<LevelState>>,
+    wind: Res<Wind>,
+    base_load_query: Query<&BaseLoad>,
+    fuel_query: Query<&FuelTank, With<ActiveLaunchPlatform>>,
 ) {
     if !*is_initialized {
         *is_initialized = true;
@@ -164,15 +172,11 @@ pub fn target_ui(
             .resizable(false)
             .anchor(egui::Align2::LEFT_TOP, egui::Vec2::new(16.0, 48.0))
             .show(egui.ctx_mut(), |ui| {
-                if level.level < LEVELS.len() - 1 {
-                    if ui.button("Next Level").clicked() {
-                        next_level.send(NextLevel(None));
-                    }
+                if level.level < LEVELS.len() - 1 && ui.button("Next Level").clicked() {
+                    next_level.send(NextLevel(None));
                 }
-                if level.level > 0 {
-                    if ui.button("Previous Level").clicked() {
-                        next_level.send(NextLevel(Some(level.level - 1)));
-                    }
+                if level.level > 0 && ui.button("Previous Level").clicked() {
+                    next_level.send(NextLevel(Some(level.level - 1)));
                 }
                 if ui.button("Retry").clicked() {
                     next_level.send(NextLevel(Some(level.level)));
@@ -187,7 +191,12 @@ Left click: Throw
 Scroll Wheel: Adjust Force
 Right click: Rotate Block
 Q/E: Throw rotation
-WASD: Move cannon",
+WASD: Move cannon
+A/D, W/S: Move crane, lower hook
+Tab: Switch cannon
+J/L: Move base (on moving levels)
+X: Detonate explosives
+M: Toggle magnet force arrows",
                 );
             });
     }
@@ -235,7 +244,7 @@ WASD: Move cannon",
                         }
                     }
 
-                    if let Some(max_blocks) = level.max_blocks {
+                    if let Some(_max_blocks) = level.max_blocks {
                         let add_one_for_aiming_block = if has_aiming_block.get_single().is_ok() {
                             1
                         } else {
@@ -257,6 +266,61 @@ WASD: Move cannon",
                             )));
                         });
                     }
+
+                    if level.wind.is_some() {
+                        let arrow = if wind.acceleration > 0.05 {
+                            "→"
+                        } else if wind.acceleration < -0.05 {
+                            "←"
+                        } else {
+                            "·"
+                        };
+                        ui.label(
+                            RichText::new(format!("Wind {} {:.1}", arrow, wind.acceleration.abs()))
+                                .size(24.0)
+                                .color(Color32::BLACK),
+                        );
+                    }
+
+                    for fuel_tank in fuel_query.iter() {
+                        let fill = if fuel_tank.ratio() < 0.2 {
+                            Color32::RED
+                        } else {
+                            Color32::DARK_GREEN
+                        };
+                        ui.add(
+                            egui::ProgressBar::new(fuel_tank.ratio())
+                                .desired_width(200.0)
+                                .fill(fill)
+                                .text(
+                                    RichText::new(format!(
+                                        "Fuel {:.0}%",
+                                        fuel_tank.ratio() * 100.0
+                                    ))
+                                    .color(Color32::WHITE),
+                                ),
+                        );
+                    }
+
+                    for load in base_load_query.iter() {
+                        let fill = if load.overloaded {
+                            Color32::RED
+                        } else {
+                            Color32::DARK_GRAY
+                        };
+                        ui.add(
+                            egui::ProgressBar::new(load.ratio().min(1.0))
+                                .desired_width(200.0)
+                                .fill(fill)
+                                .text(
+                                    RichText::new(format!(
+                                        "Load {:.0} / {:.0} kg",
+                                        load.current, load.max
+                                    ))
+                                    .color(Color32::WHITE),
+                                ),
+                        );
+                    }
                 });
             });
     }
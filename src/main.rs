@@ -1,3 +1,6 @@
+// Bevy systems take their resources and queries as arguments, long signatures are expected
+#![allow(clippy::too_many_arguments, clippy::type_complexity)]
+
 use bevy::asset::AssetMetaCheck;
 use bevy::prelude::*;
 use bevy::render::camera::ScalingMode;
@@ -9,6 +12,7 @@ use crate::base::BasePlugin;
 use crate::block::{BlockPlugin, CaughtBlock, FallingBlockCollision, SpawnTimer};
 use crate::camera_movement::{camera_movement_system, CameraMovement};
 use crate::collision_sounds::CollisionSoundPlugin;
+use crate::crane::CranePlugin;
 use crate::cursor_system::{my_cursor_system, CursorCoords};
 use crate::debris::DebrisPlugin;
 use crate::effect::EffectPlugin;
@@ -29,6 +33,7 @@ mod block;
 mod camera_movement;
 mod collision_sounds;
 mod consts;
+mod crane;
 mod cursor_system;
 mod debris;
 mod effect;
@@ -120,6 +125,7 @@ fn main() {
                 LevelIntroDialogPlugin,
                 CollisionSoundPlugin,
                 VisibilityTimerPlugin,
+                CranePlugin,
             ),
         ))
         .add_systems(Startup, (setup_graphics, setup_physics))
@@ -143,7 +149,7 @@ fn main() {
         .run();
 }
 
-pub fn setup_graphics(mut commands: Commands, mut assets: ResMut<AssetServer>) {
+pub fn setup_graphics(mut commands: Commands) {
     commands.spawn((
         Camera2dBundle {
             transform: Transform::from_xyz(0.0, 20.0, 0.0),
@@ -159,11 +165,7 @@ pub fn setup_graphics(mut commands: Commands, mut assets: ResMut<AssetServer>) {
     ));
 }
 
-pub fn setup_physics(
-    mut commands: Commands,
-    mut config: ResMut<RapierConfiguration>,
-    mut context: ResMut<RapierContext>,
-) {
+pub fn setup_physics(mut config: ResMut<RapierConfiguration>) {
     config.gravity = Vec2::Y * GRAVITY;
     config.timestep_mode = TimestepMode::Fixed {
         dt: PHYSICS_DT,
@@ -26,6 +26,7 @@ pub enum LevelState {
 pub enum GameState {
     #[default]
     Menu,
+    #[allow(dead_code)]
     Playing,
 }
 
@@ -33,7 +34,7 @@ fn enter_playing_state(
     mut commands: Commands,
     state: Res<State<LevelState>>,
     mut next_state: ResMut<NextState<LevelState>>,
-    mut all_query: Query<(Entity), With<LevelLifecycle>>,
+    mut all_query: Query<Entity, With<LevelLifecycle>>,
 ) {
     if *state == LevelState::Loading {
         for entity in all_query.iter_mut() {
@@ -21,7 +21,7 @@ pub struct TargetHeightIndicator;
 
 pub fn setup_target_height_indicator(
     mut commands: Commands,
-    mut level: Res<Level>,
+    level: Res<Level>,
     old_indicator: Query<Entity, With<TargetHeightIndicator>>,
     assets: Res<AssetServer>,
 ) {
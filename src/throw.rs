@@ -10,14 +10,17 @@ use rand::prelude::SliceRandom;
 use rand::{random, thread_rng, Rng};
 
 use crate::block::{Aiming, Block, BlockType, Falling};
-use crate::camera_movement::CameraMovement;
+use crate::crane::Crane;
 use crate::cursor_system::CursorCoords;
-use crate::effect::magnetic::{calculate_magnetic_impulse, MagneticEffect};
-use crate::launch_platform::{Barrel, LaunchPlatform};
-use crate::level::{Level, LevelStats, UpdateLevelStats};
+use crate::effect::{
+    prepare_trajectory_modifiers_system, EffectId, EffectRegistry, TrajectoryModifiers,
+};
+use crate::environment::wind::Wind;
+use crate::launch_platform::{ActiveLaunchPlatform, Barrel, LaunchPlatform, Recoil};
+use crate::level::{LaunchPlatformKind, Level, LevelStats, UpdateLevelStats};
 use crate::state::LevelState;
 use crate::visibility_timer::VisibilityTimer;
-use crate::{BARREL_LENGTH, GRAVITY, PHYSICS_DT};
+use crate::{BARREL_LENGTH, PHYSICS_DT};
 
 pub struct ThrowPlugin;
 
@@ -32,7 +35,8 @@ impl Plugin for ThrowPlugin {
         .add_systems(OnExit(LevelState::Playing), remove_simulation_system)
         .add_systems(
             PreUpdate,
-            (simulate_throw_system,)
+            (prepare_trajectory_modifiers_system, simulate_throw_system)
+                .chain()
                 .run_if(in_state(LevelState::Playing).or_else(in_state(LevelState::KeepPlaying))),
         )
         .add_systems(OnEnter(LevelState::KeepPlaying), setup_throw_queue)
@@ -105,8 +109,8 @@ pub struct TargetIndicatorBlock;
 
 pub fn remove_simulation_system(
     mut commands: Commands,
-    mut query: Query<(Entity), With<TargetIndicator>>,
-    mut aiming_block_query: Query<(Entity), With<Aiming>>,
+    mut query: Query<Entity, With<TargetIndicator>>,
+    mut aiming_block_query: Query<Entity, With<Aiming>>,
 ) {
     for entity in query.iter_mut() {
         commands.entity(entity).despawn_recursive();
@@ -116,33 +120,25 @@ pub fn remove_simulation_system(
     }
 }
 
+/// Simulation steps between two dots of the recoil drift preview
+const RECOIL_PREVIEW_SPACING: usize = 6;
+
 pub fn simulate_throw_system(
     mut commands: Commands,
     mut aim: ResMut<Aim>,
-    aimed_block: Query<
-        (Entity, &Block, &Collider, &Transform, &ReadMassProperties),
-        (With<Aiming>),
-    >,
+    aimed_block: Query<(Entity, &Block, &Collider, &Transform, &ReadMassProperties), With<Aiming>>,
     has_falling_block: Query<Entity, With<Falling>>,
     rapier_context: Res<RapierContext>,
     old_target_indicators: Query<Entity, (With<TargetIndicator>, Without<TargetIndicatorBlock>)>,
     target_indicator_block_query: Query<Entity, With<TargetIndicatorBlock>>,
-    magnets: Query<
-        (Entity, &Transform, &MagneticEffect),
-        (Without<Falling>, Without<Aiming>, With<Block>),
-    >,
-    other_blocks: Query<
-        (Entity, &Transform),
-        (
-            With<Block>,
-            Without<MagneticEffect>,
-            Without<Aiming>,
-            Without<TargetIndicator>,
-        ),
-    >,
     is_falling_block_query: Query<Entity, With<Falling>>,
-    am_i_magnet: Query<&MagneticEffect>,
-    mut assets: ResMut<AssetServer>,
+    mut modifiers: ResMut<TrajectoryModifiers>,
+    registry: Res<EffectRegistry>,
+    wind: Res<Wind>,
+    level: Res<Level>,
+    active_platform_query: Query<&LaunchPlatform, With<ActiveLaunchPlatform>>,
+    recoil_query: Query<(&Transform, &Recoil), With<ActiveLaunchPlatform>>,
+    assets: ResMut<AssetServer>,
 ) {
     // remove previous target indicators
     for entity in old_target_indicators.iter() {
@@ -156,11 +152,15 @@ pub fn simulate_throw_system(
         let dt = PHYSICS_DT;
 
         let shape = aimed_collider.clone();
-        let mut transform = aimed_transform.clone();
+        let mut transform = *aimed_transform;
         let mut velocity = aim.velocity();
-        let mut acceleration = Vec2::Y * GRAVITY;
-
-        let mut hit = false;
+        let velocity_at_launch = velocity;
+        // Falling blocks feel the full wind
+        let gravity = level.physics.gravity() + wind.acceleration();
+        let acceleration = modifiers
+            .0
+            .iter()
+            .fold(gravity, |acc, modifier| acc + modifier.acceleration());
 
         let mut steps = vec![];
 
@@ -170,7 +170,7 @@ pub fn simulate_throw_system(
         while t < 2.0 {
             let angle = Vec2::Y.angle_between((transform.rotation * Vec3::Y).xy());
 
-            let mut intersection = rapier_context.cast_shape(
+            let intersection = rapier_context.cast_shape(
                 transform.translation.xy(),
                 angle,
                 velocity.linvel,
@@ -186,52 +186,35 @@ pub fn simulate_throw_system(
                 let is_falling_block = is_falling_block_query.get(entity).is_ok();
                 if !is_falling_block {
                     transform.translation += Vec3::from((velocity.linvel * toi.toi, 0.0));
-                    hit = true;
-                    break;
-                }
-            }
 
-            let my_magnetic_effect = am_i_magnet.get(aimed).ok();
-
-            let mut impulse =
-                magnets
-                    .iter()
-                    .fold(Vec2::ZERO, |acc, (entity, magnet_transform, effect)| {
-                        let mut impulse =
-                            calculate_magnetic_impulse(magnet_transform, &transform, effect)
-                                .unwrap_or(Vec2::ZERO);
-
-                        if let Some(my_magnetic_effect) = my_magnetic_effect {
-                            impulse += calculate_magnetic_impulse(
-                                &transform,
-                                magnet_transform,
-                                my_magnetic_effect,
-                            )
-                            .unwrap_or(Vec2::ZERO);
-                        }
+                    // Every modifier has to see the hit, `any` would stop at the first one
+                    let mut keeps_moving = false;
+                    for modifier in modifiers.0.iter_mut() {
+                        keeps_moving |= modifier.on_hit(&mut velocity, &toi);
+                    }
 
-                        impulse + acc
-                    });
+                    // Without details the shapes already overlap, the prediction stops there
+                    if let (true, Some(details)) = (keeps_moving, toi.details) {
+                        // Move out of contact so the next cast doesn't hit the same collider again
+                        transform.translation += Vec3::from((details.normal1 * 0.01, 0.0));
+
+                        steps.push(transform);
+                        t += dt;
+                        continue;
+                    }
 
-            if let Some(my_magnetic_effect) = my_magnetic_effect {
-                impulse +=
-                    other_blocks
-                        .iter()
-                        .fold(Vec2::ZERO, |acc, (entity, block_transform)| {
-                            let mut impulse = calculate_magnetic_impulse(
-                                &transform,
-                                block_transform,
-                                my_magnetic_effect,
-                            )
-                            .unwrap_or(Vec2::ZERO);
-                            acc - impulse
-                        });
+                    break;
+                }
             }
 
+            let impulse = modifiers.0.iter().fold(Vec2::ZERO, |acc, modifier| {
+                acc + modifier.impulse(&transform)
+            });
+
             velocity.linvel += impulse / mass.mass;
 
             velocity.linvel += acceleration * dt;
-            transform.rotation = transform.rotation * Quat::from_rotation_z(velocity.angvel * dt);
+            transform.rotation *= Quat::from_rotation_z(velocity.angvel * dt);
             transform.translation += Vec3::from((velocity.linvel * dt, 0.0));
 
             let distance_from_origin = aimed_transform.translation.distance(transform.translation);
@@ -242,7 +225,7 @@ pub fn simulate_throw_system(
                 aim.exits_barrel_after = t;
             }
 
-            steps.push(transform.clone());
+            steps.push(transform);
 
             t += dt;
         }
@@ -250,39 +233,96 @@ pub fn simulate_throw_system(
         // println!("transform: {:?}", transform);
         // println!("velocity: {:?}", velocity);
         // println!("acceleration: {:?}", acceleration);
-        // println!("hit: {:?}", hit);
         //rapier_context.intersection_with_shape()
 
         if has_falling_block.is_empty() {
-            for step in steps {
+            if let Ok(LaunchPlatform {
+                kind: LaunchPlatformKind::Crane { .. },
+                ..
+            }) = active_platform_query.get_single()
+            {
+                // Dropped blocks fall almost straight down, a single line reads better than dots
+                let start = aimed_transform.translation.xy();
+                let offset = transform.translation.xy() - start;
+                let center = start + offset / 2.0;
                 commands.spawn((
                     SpriteBundle {
-                        transform: step,
-                        texture: assets.load("circle.png"),
+                        transform: Transform::from_xyz(center.x, center.y, 0.0)
+                            .with_rotation(Quat::from_rotation_z(Vec2::Y.angle_between(offset))),
                         sprite: Sprite {
-                            custom_size: Some(Vec2::new(0.12, 0.12)),
+                            color: Color::rgba(1.0, 1.0, 1.0, 0.6),
+                            custom_size: Some(Vec2::new(0.06, offset.length())),
                             ..Default::default()
                         },
                         ..Default::default()
                     },
                     TargetIndicator,
                 ));
+            } else {
+                for step in steps {
+                    commands.spawn((
+                        SpriteBundle {
+                            transform: step,
+                            texture: assets.load("circle.png"),
+                            sprite: Sprite {
+                                custom_size: Some(Vec2::new(0.12, 0.12)),
+                                ..Default::default()
+                            },
+                            ..Default::default()
+                        },
+                        TargetIndicator,
+                    ));
+                }
+            }
+
+            // Free platforms drift back after the throw, show where the recoil carries them
+            if let Ok((platform_transform, recoil)) = recoil_query.get_single() {
+                let mut position = platform_transform.translation.xy();
+                let mut recoil =
+                    Recoil(recoil.0 + Recoil::push(velocity_at_launch.linvel, mass.mass));
+                let mut step = 0;
+                while recoil.0 != Vec2::ZERO {
+                    position += recoil.0 * dt;
+                    recoil.fade(dt);
+                    step += 1;
+
+                    if step % RECOIL_PREVIEW_SPACING == 0 {
+                        commands.spawn((
+                            SpriteBundle {
+                                transform: Transform::from_xyz(position.x, position.y, 0.0),
+                                texture: assets.load("circle.png"),
+                                sprite: Sprite {
+                                    color: Color::rgba(1.0, 0.6, 0.2, 0.6),
+                                    custom_size: Some(Vec2::new(0.12, 0.12)),
+                                    ..Default::default()
+                                },
+                                ..Default::default()
+                            },
+                            TargetIndicator,
+                        ));
+                    }
+                }
             }
 
             let target_indicator_block = target_indicator_block_query.get_single();
             if let Ok(target_indicator_block) = target_indicator_block {
                 commands.entity(target_indicator_block).insert(transform);
             } else {
-                let mut sprite = block.sprite(&*assets);
+                let mut sprite = block.sprite(&assets);
                 sprite.transform = transform;
                 sprite.sprite.color = Color::rgba(1.0, 1.0, 1.0, 0.5);
                 commands
                     .spawn((sprite, TargetIndicator, TargetIndicatorBlock, shape, Sensor))
                     .with_children(|parent| {
-                        if let Some(effect) = block.effect_type {
-                            let mut texture = effect.texture(block.block_type);
+                        for (layer, definition) in block
+                            .effects
+                            .iter()
+                            .filter_map(|effect| registry.get(*effect))
+                            .enumerate()
+                        {
+                            let texture = definition.texture(block.block_type);
                             let sprite = SpriteBundle {
-                                transform: Transform::from_xyz(0.0, 0.0, 0.1),
+                                transform: Transform::from_xyz(0.0, 0.0, 0.1 + layer as f32 * 0.01),
                                 sprite: Sprite {
                                     color: Color::rgba(1.0, 1.0, 1.0, 0.8),
                                     custom_size: Some(Vec2::new(
@@ -305,15 +345,18 @@ pub fn simulate_throw_system(
 pub fn create_aiming_block(
     mut commands: Commands,
     mut throw_queue: ResMut<ThrowQueue>,
-    mut query: Query<(Entity), With<Aiming>>,
+    query: Query<Entity, With<Aiming>>,
     assets: Res<AssetServer>,
-    mut camera_movement: ResMut<CameraMovement>,
-    mut launch_platform_query: Query<&Transform, With<LaunchPlatform>>,
+    launch_platform_query: Query<&Transform, With<ActiveLaunchPlatform>>,
     level: Res<Level>,
 ) {
+    // The active platform is only spawned once the level starts playing
+    let Ok(launch_platform_transform) = launch_platform_query.get_single() else {
+        return;
+    };
+
     if query.iter().count() == 0 {
         if let Some(block) = throw_queue.queue.pop() {
-            let launch_platform_transform = launch_platform_query.single();
             block.spawn(
                 &mut commands,
                 Vec2::new(
@@ -321,45 +364,69 @@ pub fn create_aiming_block(
                     launch_platform_transform.translation.y + 0.0,
                 ),
                 &assets,
-                &*level,
+                &level,
             );
         }
     }
 }
 
+/// Blocks held by a crane hang from its rope instead, see `crane_hold_block_system`
 pub fn update_aiming_block_position(
     mut query: Query<(Entity, &mut Transform), (With<Aiming>, Without<LaunchPlatform>)>,
-    mut launch_platform_query: Query<&Transform, With<LaunchPlatform>>,
+    launch_platform_query: Query<
+        &Transform,
+        (
+            With<ActiveLaunchPlatform>,
+            With<LaunchPlatform>,
+            Without<Crane>,
+        ),
+    >,
 ) {
-    let launch_platform_transform = launch_platform_query.single();
-    for (entity, mut transform) in query.iter_mut() {
+    let Ok(launch_platform_transform) = launch_platform_query.get_single() else {
+        return;
+    };
+    for (_entity, mut transform) in query.iter_mut() {
         transform.translation.x = launch_platform_transform.translation.x;
         transform.translation.y = launch_platform_transform.translation.y + 0.0;
     }
 }
 
-fn throw_queue_item(level: &Level) -> Block {
-    let effect = if random::<f32>() < level.effect_likelihood {
-        level
+fn throw_queue_item(level: &Level, registry: &EffectRegistry) -> Block {
+    let mut effects: Vec<EffectId> = vec![];
+    for _ in 0..level.max_effects {
+        if random::<f32>() >= level.effect_likelihood {
+            continue;
+        }
+
+        let effect = level
             .enabled_effects
-            .choose_weighted(&mut thread_rng(), |(effect, weight)| *weight)
+            .choose_weighted(&mut thread_rng(), |(_effect, weight)| *weight)
             .ok()
-            .map(|(effect, _)| *effect)
-    } else {
-        None
-    };
+            .map(|(effect, _)| *effect);
+
+        if let Some(effect) = effect {
+            if !effects
+                .iter()
+                .any(|other| registry.conflicts(*other, effect))
+            {
+                effects.push(effect);
+            }
+        }
+    }
+
     let block = BlockType::random();
     let variant = thread_rng().gen_range(1..=5);
 
     let initial_rotation = thread_rng().gen_range(0..4) as f32 * PI / 2.0;
 
-    Block::new(block, variant, effect, initial_rotation)
+    Block::new(block, variant, effects, initial_rotation)
 }
 
 pub fn setup_throw_queue(
     mut throw_queue: ResMut<ThrowQueue>,
     level: Res<Level>,
     level_state: Res<State<LevelState>>,
+    registry: Res<EffectRegistry>,
 ) {
     *throw_queue = ThrowQueue::default();
 
@@ -370,7 +437,7 @@ pub fn setup_throw_queue(
 
     if let Some(max_blocks) = level.max_blocks {
         for _ in 0..max_blocks {
-            throw_queue.queue.push(throw_queue_item(&level));
+            throw_queue.queue.push(throw_queue_item(&level, &registry));
         }
         throw_queue.target_length = 0;
     } else {
@@ -381,18 +448,14 @@ pub fn setup_throw_queue(
 pub fn fill_throw_queue(
     mut throw_queue: ResMut<ThrowQueue>,
     level: Res<Level>,
-    level_stats: Res<LevelStats>,
+    registry: Res<EffectRegistry>,
 ) {
     while throw_queue.queue.len() < throw_queue.target_length {
-        throw_queue.queue.push(throw_queue_item(&level));
+        throw_queue.queue.push(throw_queue_item(&level, &registry));
     }
 }
 
-pub fn update_aim_system(
-    mut aim: ResMut<Aim>,
-    mut query: Query<&mut Transform, With<Aiming>>,
-    key_code: Res<Input<KeyCode>>,
-) {
+pub fn update_aim_system(mut aim: ResMut<Aim>, key_code: Res<Input<KeyCode>>) {
     if key_code.pressed(KeyCode::Left) {
         aim.direction = aim.direction.rotate(Vec2::from_angle(0.005));
     } else if key_code.pressed(KeyCode::Right) {
@@ -414,19 +477,28 @@ pub fn update_aim_system(
 
 pub fn update_aim_from_mouse_position_system(
     mut aim: ResMut<Aim>,
-    mut query: Query<&mut Transform, With<Aiming>>,
+    query: Query<&mut Transform, With<Aiming>>,
     mouse_position: Res<CursorCoords>,
-    mut input: ResMut<Input<KeyCode>>,
+    active_platform_query: Query<&LaunchPlatform, With<ActiveLaunchPlatform>>,
+    level: Res<Level>,
 ) {
+    // The crane aims by moving its hook, see `crane_aim_system`
+    if let Ok(LaunchPlatform {
+        kind: LaunchPlatformKind::Crane { .. },
+        ..
+    }) = active_platform_query.get_single()
+    {
+        return;
+    }
+
+    let gravity = level.physics.gravity;
     let mouse_position = mouse_position.0;
-    if let Ok(mut transform) = query.get_single() {
+    if let Ok(transform) = query.get_single() {
         // let force =
         //     calculate_velocity_for_throw(transform.translation.xy(), mouse_position, aim.direction);
         //
         // aim.force = force;
 
-        let mut shot = None;
-
         let mut min_force = 0.0;
 
         let force_factor = aim.force_factor;
@@ -442,11 +514,12 @@ pub fn update_aim_from_mouse_position_system(
             // // True when the mouse is in a 45 degree angle below the platform
             // let use_direct_aim = angle < PI / 4.0 && angle > -PI / 4.0;
 
-            shot = calculate_shot_for_target(
+            let shot = calculate_shot_for_target(
                 transform.translation.xy(),
                 mouse_position,
                 min_force,
                 direct_aim,
+                gravity,
             );
             if shot.is_none() {
                 min_force += 0.006;
@@ -457,11 +530,12 @@ pub fn update_aim_from_mouse_position_system(
 
         let force = min_force + min_force * force_factor.abs();
 
-        shot = calculate_shot_for_target(
+        let shot = calculate_shot_for_target(
             transform.translation.xy(),
             mouse_position,
             force,
             direct_aim,
+            gravity,
         );
 
         if let Some(shot) = shot {
@@ -493,10 +567,11 @@ fn calculate_shot_for_target(
     launch_pos: Vec2,
     velocity: f32,
     use_direct_aim: bool,
+    gravity: f32,
 ) -> Option<Vec2> {
     let xp = target.x - launch_pos.x;
     let y = target.y - launch_pos.y;
-    let g = GRAVITY;
+    let g = gravity;
     let v = velocity;
     let angle1: f32;
     let angle2: f32;
@@ -520,14 +595,16 @@ fn calculate_shot_for_target(
 
 pub fn throw_system(
     mut commands: Commands,
-    mut input: ResMut<Input<KeyCode>>,
-    mut mouse_button_input: ResMut<Input<MouseButton>>,
-    mut touch_input: ResMut<Touches>,
+    input: ResMut<Input<KeyCode>>,
+    mouse_button_input: ResMut<Input<MouseButton>>,
+    touch_input: ResMut<Touches>,
     mut aim: ResMut<Aim>,
-    mut query: Query<(Entity), With<Aiming>>,
+    mut query: Query<(Entity, &ReadMassProperties, Has<ImpulseJoint>), With<Aiming>>,
     mut update_level_stats_event: EventWriter<UpdateLevelStats>,
     target_indicator_block_query: Query<Entity, With<TargetIndicatorBlock>>,
-    mut barrel: Query<Entity, With<Barrel>>,
+    barrel: Query<(Entity, &Parent), With<Barrel>>,
+    active_platform_query: Query<Entity, With<ActiveLaunchPlatform>>,
+    mut recoil_query: Query<(&mut Velocity, &mut Recoil), With<ActiveLaunchPlatform>>,
     throw_queue: Res<ThrowQueue>,
     mut level_stats: ResMut<LevelStats>,
 ) {
@@ -535,21 +612,39 @@ pub fn throw_system(
         || mouse_button_input.just_pressed(MouseButton::Left)
         || (touch_input.any_just_released() && touch_input.iter().count() == 0)
     {
-        for entity in query.iter_mut() {
-            commands
-                .entity(entity)
-                .remove::<Aiming>()
-                .remove::<Sensor>()
-                .insert((
-                    Falling,
-                    RigidBody::Dynamic,
-                    Sleeping::disabled(),
-                    aim.velocity(),
-                    VisibilityTimer(Timer::new(
-                        Duration::from_secs_f32(aim.exits_barrel_after),
-                        TimerMode::Once,
-                    )),
-                ));
+        for (entity, mass, on_crane) in query.iter_mut() {
+            // Blocks hanging from a crane are simply let go, they keep their swing
+            if on_crane {
+                commands
+                    .entity(entity)
+                    .remove::<Aiming>()
+                    .remove::<ImpulseJoint>()
+                    .insert(Falling);
+            } else {
+                let velocity = aim.velocity();
+
+                // Momentum is conserved, free platforms are pushed back by the throw
+                if let Ok((mut platform_velocity, mut recoil)) = recoil_query.get_single_mut() {
+                    let push = Recoil::push(velocity.linvel, mass.mass);
+                    platform_velocity.linvel += push;
+                    recoil.0 += push;
+                }
+
+                commands
+                    .entity(entity)
+                    .remove::<Aiming>()
+                    .remove::<Sensor>()
+                    .insert((
+                        Falling,
+                        RigidBody::Dynamic,
+                        Sleeping::disabled(),
+                        velocity,
+                        VisibilityTimer(Timer::new(
+                            Duration::from_secs_f32(aim.exits_barrel_after),
+                            TimerMode::Once,
+                        )),
+                    ));
+            }
 
             update_level_stats_event.send(UpdateLevelStats::BlockThrown);
 
@@ -562,7 +657,10 @@ pub fn throw_system(
             commands.entity(entity).despawn_recursive();
         }
 
-        for entity in barrel.iter() {
+        for (entity, _) in barrel
+            .iter()
+            .filter(|(_, parent)| active_platform_query.contains(parent.get()))
+        {
             let tween = Tween::new(
                 EaseFunction::QuadraticInOut,
                 Duration::from_secs_f32(0.125),
@@ -7,7 +7,7 @@ pub struct UiPlugin;
 
 impl Plugin for UiPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, (update_ui_scale_factor));
+        app.add_systems(Update, update_ui_scale_factor);
         app.add_systems(
             PreUpdate,
             (absorb_egui_inputs,)
@@ -28,7 +28,7 @@ fn update_ui_scale_factor(
     mut egui_settings: ResMut<EguiSettings>,
     windows: Query<&Window, With<PrimaryWindow>>,
 ) {
-    if let Ok(window) = windows.get_single() {
+    if let Ok(_window) = windows.get_single() {
         egui_settings.scale_factor = 1.25;
     }
 }